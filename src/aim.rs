@@ -0,0 +1,256 @@
+use osu_db::{Mod, Replay};
+use rosu_pp::{parse::Pos2, Beatmap, BeatmapExt};
+
+use crate::{difficulty::circle_radius, error::UrError, report::HitErrorReport};
+
+/// Where the cursor was when an object was hit.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct AimOffset {
+    /// Index of the object within the map's hit objects.
+    pub index: usize,
+    /// Position of the cursor at the hit.
+    pub pos: Pos2,
+    /// Stacked position of the object's center.
+    pub center: Pos2,
+    /// Stacked end position of the previous object, `None` for the first object.
+    pub prev_end: Option<Pos2>,
+}
+
+impl AimOffset {
+    /// Offset of the cursor to the object's center in osu!pixels.
+    pub fn offset(&self) -> Pos2 {
+        self.pos - self.center
+    }
+
+    /// Offset of the cursor relative to the direction the cursor came from.
+    ///
+    /// `x` is along the movement from the previous object to this one so positive values
+    /// overshoot and negative values undershoot. `y` is across the movement with positive
+    /// values to its right.
+    ///
+    /// `None` for the first object or if the previous object ends at this object's center.
+    pub fn directional_offset(&self) -> Option<Pos2> {
+        let direction = self.center - self.prev_end?;
+
+        if direction.length_squared() <= f32::EPSILON {
+            return None;
+        }
+
+        let direction = direction.normalize();
+        let offset = self.offset();
+
+        Some(Pos2 {
+            x: offset.dot(direction),
+            y: direction.x * offset.y - direction.y * offset.x,
+        })
+    }
+}
+
+/// Statistics over a list of aim offsets.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct AimStatistics {
+    /// Average offset i.e. the center of the hit distribution.
+    pub mean: Pos2,
+    /// Average distance of the hits to the object's center.
+    pub mean_radial_error: f64,
+    /// Variance of the `x` offsets.
+    pub var_x: f64,
+    /// Variance of the `y` offsets.
+    pub var_y: f64,
+    /// Covariance of the `x` and `y` offsets.
+    pub cov_xy: f64,
+    /// Length of the covariance ellipse's major half-axis i.e. one standard deviation along
+    /// the direction of the largest spread.
+    pub semi_major: f64,
+    /// Length of the covariance ellipse's minor half-axis.
+    pub semi_minor: f64,
+    /// Angle of the major axis to the `x` axis in radians.
+    pub angle: f64,
+}
+
+impl AimStatistics {
+    pub fn new(offsets: &[Pos2]) -> Self {
+        let len = offsets.len() as f64;
+
+        let mut sum_x = 0.0;
+        let mut sum_y = 0.0;
+        let mut sum_radial = 0.0;
+
+        for offset in offsets {
+            sum_x += f64::from(offset.x);
+            sum_y += f64::from(offset.y);
+            sum_radial += f64::from(offset.length());
+        }
+
+        let mean_x = sum_x / len;
+        let mean_y = sum_y / len;
+
+        let mut var_x = 0.0;
+        let mut var_y = 0.0;
+        let mut cov_xy = 0.0;
+
+        for offset in offsets {
+            let dx = f64::from(offset.x) - mean_x;
+            let dy = f64::from(offset.y) - mean_y;
+
+            var_x += dx * dx;
+            var_y += dy * dy;
+            cov_xy += dx * dy;
+        }
+
+        var_x /= len;
+        var_y /= len;
+        cov_xy /= len;
+
+        // eigenvalues of the covariance matrix
+        let half_trace = (var_x + var_y) / 2.0;
+        let spread = (((var_x - var_y) / 2.0).powi(2) + cov_xy * cov_xy).sqrt();
+
+        Self {
+            mean: Pos2 {
+                x: mean_x as f32,
+                y: mean_y as f32,
+            },
+            mean_radial_error: sum_radial / len,
+            var_x,
+            var_y,
+            cov_xy,
+            semi_major: (half_trace + spread).sqrt(),
+            semi_minor: (half_trace - spread).max(0.0).sqrt(),
+            angle: (2.0 * cov_xy).atan2(var_x - var_y) / 2.0,
+        }
+    }
+}
+
+/// Aim analysis of an osu!standard replay.
+///
+/// Contains the cursor position of every object with a hit error of a [`HitErrorReport`].
+#[derive(Clone, Debug, Default)]
+pub struct AimReport {
+    /// Aim offset of each hit object, ordered by object index.
+    pub offsets: Vec<AimOffset>,
+    /// Radius of the map's circles in osu!pixels, as used to hit test the objects.
+    pub radius: f64,
+}
+
+impl AimReport {
+    /// The size of the heatmap on osu!lazer's results screen.
+    pub const LAZER_HEATMAP_SIZE: usize = 20;
+
+    /// Collect the cursor positions of the report's hit errors.
+    ///
+    /// The report must have been calculated on the same map and replay.
+    pub fn new(map: &Beatmap, replay: &Replay, report: &HitErrorReport) -> Result<Self, UrError> {
+        UrError::check_osu_mode(map, replay)?;
+
+        let mods = replay
+            .mods
+            .without(Mod::DoubleTime)
+            .without(Mod::HalfTime)
+            .without(Mod::Nightcore);
+
+        let cs = map.attributes().mods(mods.bits()).build().cs;
+        let radius = f64::from(circle_radius(cs));
+        let hit_objects = map.osu_hitobjects(mods.bits());
+
+        let offsets = report
+            .hit_errors
+            .iter()
+            .filter_map(|hit_error| {
+                let h = hit_objects.get(hit_error.index)?;
+
+                let prev_end = hit_error
+                    .index
+                    .checked_sub(1)
+                    .map(|i| hit_objects[i].stacked_end_pos());
+
+                Some(AimOffset {
+                    index: hit_error.index,
+                    pos: hit_error.pos?,
+                    center: h.stacked_pos(),
+                    prev_end,
+                })
+            })
+            .collect();
+
+        Ok(Self { offsets, radius })
+    }
+
+    /// [`AimStatistics`] over the offsets to the objects' centers.
+    pub fn statistics(&self) -> AimStatistics {
+        let offsets: Vec<_> = self.offsets.iter().map(AimOffset::offset).collect();
+
+        AimStatistics::new(&offsets)
+    }
+
+    /// [`AimStatistics`] over the offsets relative to the movement direction.
+    ///
+    /// Offsets without a direction are left out, see [`AimOffset::directional_offset`].
+    pub fn directional_statistics(&self) -> AimStatistics {
+        let offsets: Vec<_> = self
+            .offsets
+            .iter()
+            .filter_map(AimOffset::directional_offset)
+            .collect();
+
+        AimStatistics::new(&offsets)
+    }
+
+    /// The hit distribution that osu!lazer draws on its results screen.
+    ///
+    /// Each hit is rotated such that the previous object lies to the bottom left, scaled by the
+    /// circle radius, and counted in a grid of `size` × `size` cells indexed by row then
+    /// column. The circle's edge lies at 80% of the grid's half width.
+    pub fn heatmap(&self, size: usize) -> Vec<Vec<usize>> {
+        let mut grid = vec![vec![0; size]; size];
+
+        if size == 0 {
+            return grid;
+        }
+
+        for offset in self.offsets.iter() {
+            let Some(start) = offset.prev_end else {
+                continue;
+            };
+
+            let (start, end, hit) = (start, offset.center, offset.pos);
+
+            /*
+                double angle1 = Math.Atan2(end.Y - hitPoint.Y, hitPoint.X - end.X);
+                double angle2 = Math.Atan2(end.Y - start.Y, start.X - end.X);
+                double finalAngle = angle2 - angle1;
+                float normalisedDistance = Vector2.Distance(hitPoint, end) / radius;
+
+                double rotatedAngle = finalAngle - MathUtils.DegreesToRadians(rotation);
+                var rotatedCoordinate = -1 * new Vector2((float)Math.Cos(rotatedAngle), (float)Math.Sin(rotatedAngle));
+
+                Vector2 localCentre = new Vector2(points_per_dimension - 1) / 2;
+                float localRadius = localCentre.X * inner_portion * normalisedDistance;
+                Vector2 localPoint = localCentre + localRadius * rotatedCoordinate;
+            */
+
+            let angle1 = f64::from(end.y - hit.y).atan2(f64::from(hit.x - end.x));
+            let angle2 = f64::from(end.y - start.y).atan2(f64::from(start.x - end.x));
+            let final_angle = angle2 - angle1;
+            let normalized_distance = f64::from(hit.distance(end)) / self.radius;
+
+            let rotated_angle = final_angle - HEATMAP_ROTATION.to_radians();
+
+            let local_centre = (size - 1) as f64 / 2.0;
+            let local_radius = local_centre * HEATMAP_INNER_PORTION * normalized_distance;
+            let x = local_centre - local_radius * rotated_angle.cos();
+            let y = local_centre - local_radius * rotated_angle.sin();
+
+            let max = (size - 1) as f64;
+            let row = y.round().clamp(0.0, max) as usize;
+            let column = x.round().clamp(0.0, max) as usize;
+
+            grid[row][column] += 1;
+        }
+
+        grid
+    }
+}
+
+const HEATMAP_ROTATION: f64 = 45.0;
+const HEATMAP_INNER_PORTION: f64 = 0.8;
@@ -0,0 +1,219 @@
+use osu_db::{Mode, Replay};
+use rosu_pp::Beatmap;
+
+use crate::{
+    error::UrError,
+    impls::{
+        baseline::BaselineCalculator,
+        catch::CatchCalculator,
+        circleguard::CircleguardCalculator,
+        custom1::Custom1Calculator,
+        custom2::Custom2Calculator,
+        iters::ItersCalculator,
+        lazer::{HitPolicy, LazerCalculator},
+        mania::ManiaCalculator,
+        stable::StableCalculator,
+        taiko::TaikoCalculator,
+    },
+    options::UrOptions,
+    report::HitErrorReport,
+};
+
+/// A way of detecting hits in a replay to calculate its unstable rate.
+pub trait UrCalculator {
+    /// Calculate the [`HitErrorReport`] of the replay on the map with the given [`UrOptions`]
+    /// or return why it couldn't be calculated.
+    fn try_calculate_report_with(
+        &self,
+        map: &Beatmap,
+        replay: &Replay,
+        options: &UrOptions,
+    ) -> Result<HitErrorReport, UrError>;
+
+    /// Calculate the [`HitErrorReport`] of the replay on the map or return why it couldn't be
+    /// calculated.
+    fn try_calculate_report(
+        &self,
+        map: &Beatmap,
+        replay: &Replay,
+    ) -> Result<HitErrorReport, UrError> {
+        self.try_calculate_report_with(map, replay, &UrOptions::default())
+    }
+
+    /// Calculate the unstable rate of the replay on the map or return why it couldn't be
+    /// calculated.
+    fn try_calculate_ur(&self, map: &Beatmap, replay: &Replay) -> Result<f64, UrError> {
+        self.try_calculate_report(map, replay)
+            .map(|report| report.unstable_rate)
+    }
+
+    /// Calculate the [`HitErrorReport`] of the replay on the map.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the calculation fails, see [`UrCalculator::try_calculate_report`].
+    fn calculate_report(&self, map: &Beatmap, replay: &Replay) -> HitErrorReport {
+        self.calculate_report_with(map, replay, &UrOptions::default())
+    }
+
+    /// Calculate the [`HitErrorReport`] of the replay on the map with the given [`UrOptions`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the calculation fails, see [`UrCalculator::try_calculate_report_with`].
+    fn calculate_report_with(
+        &self,
+        map: &Beatmap,
+        replay: &Replay,
+        options: &UrOptions,
+    ) -> HitErrorReport {
+        self.try_calculate_report_with(map, replay, options)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Calculate the unstable rate of the replay on the map.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the calculation fails, see [`UrCalculator::try_calculate_ur`].
+    fn calculate_ur(&self, map: &Beatmap, replay: &Replay) -> f64 {
+        self.calculate_report(map, replay).unstable_rate
+    }
+}
+
+/// All available [`UrCalculator`]s.
+///
+/// The default algorithm can be chosen at compile time through the cargo feature of the same
+/// name and is [`Algorithm::Custom2`] otherwise.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Algorithm {
+    Baseline,
+    Iters,
+    Stable,
+    Circleguard,
+    Custom1,
+    Custom2,
+    Lazer,
+}
+
+impl Algorithm {
+    /// All algorithms in order of declaration.
+    pub const ALL: [Self; 7] = [
+        Self::Baseline,
+        Self::Iters,
+        Self::Stable,
+        Self::Circleguard,
+        Self::Custom1,
+        Self::Custom2,
+        Self::Lazer,
+    ];
+
+    /// Lowercase name of the algorithm, same as its cargo feature.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Baseline => "baseline",
+            Self::Iters => "iters",
+            Self::Stable => "stable",
+            Self::Circleguard => "circleguard",
+            Self::Custom1 => "custom1",
+            Self::Custom2 => "custom2",
+            Self::Lazer => "lazer",
+        }
+    }
+
+    /// The algorithm with the given [`name`](Algorithm::name), if any.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|algorithm| algorithm.name().eq_ignore_ascii_case(name))
+    }
+
+    /// The [`UrCalculator`] that implements the algorithm.
+    pub fn calculator(self) -> &'static dyn UrCalculator {
+        match self {
+            Self::Baseline => &BaselineCalculator,
+            Self::Iters => &ItersCalculator,
+            Self::Stable => &StableCalculator,
+            Self::Circleguard => &CircleguardCalculator,
+            Self::Custom1 => &Custom1Calculator,
+            Self::Custom2 => &Custom2Calculator,
+            Self::Lazer => &LazerCalculator {
+                hit_policy: HitPolicy::StartTimeOrdered,
+            },
+        }
+    }
+}
+
+impl UrCalculator for Algorithm {
+    fn try_calculate_report_with(
+        &self,
+        map: &Beatmap,
+        replay: &Replay,
+        options: &UrOptions,
+    ) -> Result<HitErrorReport, UrError> {
+        self.calculator()
+            .try_calculate_report_with(map, replay, options)
+    }
+}
+
+/// Picks the [`UrCalculator`] based on the mode the replay was played in.
+///
+/// osu!standard replays use the [`Algorithm`] while osu!taiko, osu!catch, and osu!mania
+/// replays use [`TaikoCalculator`], [`CatchCalculator`], and [`ManiaCalculator`]. Maps must
+/// either be of the replay's mode or osu!standard maps that convert to it, otherwise the
+/// calculation fails with [`UrError::ModeMismatch`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct AnyModeCalculator {
+    /// The algorithm for osu!standard replays.
+    pub algorithm: Algorithm,
+}
+
+impl AnyModeCalculator {
+    /// The [`UrCalculator`] for the replay's mode.
+    pub fn calculator(self, replay: &Replay) -> &'static dyn UrCalculator {
+        match replay.mode {
+            Mode::Standard => self.algorithm.calculator(),
+            Mode::Taiko => &TaikoCalculator,
+            Mode::CatchTheBeat => &CatchCalculator,
+            Mode::Mania => &ManiaCalculator,
+        }
+    }
+}
+
+impl UrCalculator for AnyModeCalculator {
+    fn try_calculate_report_with(
+        &self,
+        map: &Beatmap,
+        replay: &Replay,
+        options: &UrOptions,
+    ) -> Result<HitErrorReport, UrError> {
+        self.calculator(replay)
+            .try_calculate_report_with(map, replay, options)
+    }
+}
+
+macro_rules! default_algorithm {
+    ( $( $feature:literal -> $variant:ident ,)* ) => {
+        impl Default for Algorithm {
+            fn default() -> Self {
+                $(
+                    if cfg!(feature = $feature) {
+                        return Self::$variant;
+                    }
+                )*
+
+                Self::Custom2
+            }
+        }
+    }
+}
+
+default_algorithm! {
+    "baseline" -> Baseline,
+    "iters" -> Iters,
+    "stable" -> Stable,
+    "circleguard" -> Circleguard,
+    "custom1" -> Custom1,
+    "custom2" -> Custom2,
+    "lazer" -> Lazer,
+}
@@ -0,0 +1,218 @@
+use std::{
+    any::Any,
+    error::Error as StdError,
+    fmt::{Display, Formatter, Result as FmtResult},
+    fs, io,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread,
+};
+
+use osu_db::Replay;
+use rosu_pp::Beatmap;
+
+use crate::{
+    algorithm::UrCalculator,
+    beatmap_index::{find_files, verify_beatmap, BeatmapIndex},
+    error::UrError,
+    options::UrOptions,
+    report::HitErrorReport,
+};
+
+/// Settings for [`calculate_batch`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BatchOptions {
+    /// Settings of each calculation.
+    pub options: UrOptions,
+    /// Amount of worker threads, defaults to the available parallelism.
+    pub threads: Option<usize>,
+    /// Calculate on beatmaps whose content no longer matches the hash they were found by,
+    /// e.g. with an outdated index, instead of failing with [`UrError::BeatmapMismatch`].
+    pub allow_modified_beatmaps: bool,
+}
+
+/// The outcome of a single replay of a batch.
+#[derive(Clone, Debug)]
+pub struct BatchEntry {
+    /// Path of the `.osr` file.
+    pub replay_path: PathBuf,
+    /// The replay without its frames, `None` if it could not be read.
+    pub replay: Option<Replay>,
+    /// Path of the replay's beatmap, `None` if it was not found.
+    pub map_path: Option<PathBuf>,
+    /// The calculated report or why there is none.
+    pub result: Result<HitErrorReport, BatchError>,
+}
+
+/// Reasons why a replay of a batch could not be calculated.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BatchError {
+    /// The `.osr` file could not be read or parsed.
+    ReadReplay(String),
+    /// The replay does not store the hash of its beatmap.
+    MissingBeatmapHash,
+    /// No beatmap with the replay's beatmap hash is in the index.
+    BeatmapNotFound(String),
+    /// The `.osu` file could not be read or parsed.
+    ReadBeatmap(String),
+    /// The calculation failed.
+    Calculate(UrError),
+    /// The calculation panicked, with the panic's message if it had one.
+    Panic(String),
+}
+
+impl Display for BatchError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::ReadReplay(reason) => write!(f, "failed to read replay: {reason}"),
+            Self::MissingBeatmapHash => f.write_str("replay does not contain a beatmap hash"),
+            Self::BeatmapNotFound(hash) => write!(f, "no beatmap with hash {hash} was found"),
+            Self::ReadBeatmap(reason) => write!(f, "failed to read beatmap: {reason}"),
+            Self::Calculate(err) => err.fmt(f),
+            Self::Panic(msg) => write!(f, "calculation panicked: {msg}"),
+        }
+    }
+}
+
+impl StdError for BatchError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            Self::Calculate(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<UrError> for BatchError {
+    fn from(err: UrError) -> Self {
+        Self::Calculate(err)
+    }
+}
+
+/// All `.osr` files within the directory and its subdirectories, ordered by path.
+pub fn find_replays(dir: impl AsRef<Path>) -> io::Result<Vec<PathBuf>> {
+    find_files(dir.as_ref(), "osr")
+}
+
+/// Calculate each replay on the beatmap of the index that matches its beatmap hash.
+///
+/// Replays are spread across threads and failures are recorded per replay instead of
+/// aborting the batch, including calculations that panic. Entries are in the same order as
+/// the paths.
+pub fn calculate_batch<C: UrCalculator + Sync>(
+    calculator: &C,
+    replay_paths: &[PathBuf],
+    index: &BeatmapIndex,
+    options: &BatchOptions,
+) -> Vec<BatchEntry> {
+    let n_threads = options
+        .threads
+        .or_else(|| thread::available_parallelism().ok().map(usize::from))
+        .unwrap_or(1)
+        .clamp(1, replay_paths.len().max(1));
+
+    let next = AtomicUsize::new(0);
+    let entries = Mutex::new(Vec::with_capacity(replay_paths.len()));
+
+    thread::scope(|scope| {
+        for _ in 0..n_threads {
+            scope.spawn(|| loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+
+                let Some(replay_path) = replay_paths.get(i) else {
+                    break;
+                };
+
+                let entry = panic::catch_unwind(AssertUnwindSafe(|| {
+                    calculate_entry(calculator, replay_path, index, options)
+                }))
+                .unwrap_or_else(|payload| BatchEntry {
+                    replay_path: replay_path.to_owned(),
+                    replay: None,
+                    map_path: None,
+                    result: Err(BatchError::Panic(panic_message(payload.as_ref()))),
+                });
+
+                entries.lock().unwrap().push((i, entry));
+            });
+        }
+    });
+
+    let mut entries = entries.into_inner().unwrap();
+    entries.sort_unstable_by_key(|(i, _)| *i);
+
+    entries.into_iter().map(|(_, entry)| entry).collect()
+}
+
+/// Panics carry a `&str` or a `String` unless they were raised with a custom payload.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(msg) = payload.downcast_ref::<&str>() {
+        (*msg).to_owned()
+    } else if let Some(msg) = payload.downcast_ref::<String>() {
+        msg.clone()
+    } else {
+        "unknown panic".to_owned()
+    }
+}
+
+fn calculate_entry<C: UrCalculator>(
+    calculator: &C,
+    replay_path: &Path,
+    index: &BeatmapIndex,
+    options: &BatchOptions,
+) -> BatchEntry {
+    let mut entry = BatchEntry {
+        replay_path: replay_path.to_owned(),
+        replay: None,
+        map_path: None,
+        result: Err(BatchError::MissingBeatmapHash),
+    };
+
+    let mut replay = match Replay::from_file(replay_path) {
+        Ok(replay) => replay,
+        Err(err) => {
+            entry.result = Err(BatchError::ReadReplay(err.to_string()));
+
+            return entry;
+        }
+    };
+
+    entry.map_path = index.find(&replay).map(Path::to_owned);
+
+    entry.result = match (&replay.beatmap_hash, &entry.map_path) {
+        (None, _) => Err(BatchError::MissingBeatmapHash),
+        (Some(hash), None) => Err(BatchError::BeatmapNotFound(hash.clone())),
+        (Some(_), Some(map_path)) => calculate_map(calculator, map_path, &replay, options),
+    };
+
+    // frames are only needed for the calculation
+    replay.replay_data = None;
+    replay.raw_replay_data = None;
+    entry.replay = Some(replay);
+
+    entry
+}
+
+fn calculate_map<C: UrCalculator>(
+    calculator: &C,
+    map_path: &Path,
+    replay: &Replay,
+    options: &BatchOptions,
+) -> Result<HitErrorReport, BatchError> {
+    let bytes = fs::read(map_path).map_err(|err| BatchError::ReadBeatmap(err.to_string()))?;
+
+    if !options.allow_modified_beatmaps {
+        verify_beatmap(&bytes, replay)?;
+    }
+
+    let map =
+        Beatmap::from_bytes(&bytes).map_err(|err| BatchError::ReadBeatmap(err.to_string()))?;
+
+    calculator
+        .try_calculate_report_with(&map, replay, &options.options)
+        .map_err(BatchError::from)
+}
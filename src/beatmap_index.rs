@@ -0,0 +1,251 @@
+use std::{
+    collections::HashMap,
+    fs,
+    io::{self, BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+};
+
+use md5::{Digest, Md5};
+use osu_db::{Error as OsuDbError, Listing, Replay};
+
+use crate::error::UrError;
+
+/// Where osu!stable keeps its beatmaps unless configured otherwise.
+const DEFAULT_SONGS_DIR: &str = "Songs";
+
+/// Paths of `.osu` files by the MD5 hash of their content, as stored in
+/// [`Replay::beatmap_hash`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BeatmapIndex {
+    paths: HashMap<String, PathBuf>,
+}
+
+impl BeatmapIndex {
+    /// An empty index.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Hash every `.osu` file within the directory and its subdirectories, e.g. the `Songs`
+    /// folder of an osu! installation.
+    ///
+    /// Paths are absolute so that a [written](BeatmapIndex::write_index) index can be read
+    /// from anywhere. `.osu` files that can't be read, e.g. broken links, are left out rather
+    /// than failing the whole index.
+    pub fn from_songs_dir(dir: impl AsRef<Path>) -> io::Result<Self> {
+        let mut index = Self::new();
+
+        for path in find_files(&fs::canonicalize(dir)?, "osu")? {
+            let Ok(bytes) = fs::read(&path) else {
+                continue;
+            };
+
+            index.insert(beatmap_hash(&bytes), path);
+        }
+
+        Ok(index)
+    }
+
+    /// Read an index with one `<md5> <path>` line per beatmap, as written by
+    /// [`BeatmapIndex::write_index`].
+    ///
+    /// Relative paths are relative to the directory of the index file. Empty lines and lines
+    /// starting with `#` are ignored.
+    pub fn from_index_file(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let base = path.parent().unwrap_or_else(|| Path::new(""));
+        let reader = BufReader::new(fs::File::open(path)?);
+        let mut index = Self::new();
+
+        for (i, line) in reader.lines().enumerate() {
+            let line = line?;
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let Some((hash, map_path)) = line.split_once(char::is_whitespace) else {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("expected `<md5> <path>` on line {}", i + 1),
+                ));
+            };
+
+            index.insert(hash, base.join(map_path.trim_start()));
+        }
+
+        Ok(index)
+    }
+
+    /// Write one `<md5> <path>` line per beatmap, ordered by hash.
+    pub fn write_index(&self, mut writer: impl Write) -> io::Result<()> {
+        let mut entries: Vec<_> = self.paths.iter().collect();
+        entries.sort_unstable();
+
+        for (hash, path) in entries {
+            writeln!(writer, "{hash} {}", path.display())?;
+        }
+
+        Ok(())
+    }
+
+    /// Paths of the beatmaps in an `osu!.db` listing, located within the songs directory.
+    ///
+    /// Beatmaps without a hash, folder, or file name are left out.
+    pub fn from_listing(listing: &Listing, songs_dir: impl AsRef<Path>) -> Self {
+        let songs_dir = songs_dir.as_ref();
+        let mut index = Self::new();
+
+        for beatmap in listing.beatmaps.iter() {
+            if let (Some(hash), Some(folder_name), Some(file_name)) =
+                (&beatmap.hash, &beatmap.folder_name, &beatmap.file_name)
+            {
+                index.insert(hash.as_str(), songs_dir.join(folder_name).join(file_name));
+            }
+        }
+
+        index
+    }
+
+    /// Read the `osu!.db` of an osu!stable installation.
+    ///
+    /// Beatmaps are located in the `BeatmapDirectory` of the user's `osu!.<name>.cfg` and in
+    /// the `Songs` folder otherwise. Unlike [`BeatmapIndex::from_songs_dir`], no file is read
+    /// or hashed so the index reflects the state of the last time osu! saved its database.
+    pub fn from_osu_dir(osu_dir: impl AsRef<Path>) -> io::Result<Self> {
+        let osu_dir = osu_dir.as_ref();
+
+        let listing = Listing::from_file(osu_dir.join("osu!.db")).map_err(|err| match err {
+            OsuDbError::Io(err) => err,
+            err => io::Error::new(io::ErrorKind::InvalidData, err.to_string()),
+        })?;
+
+        let songs_dir =
+            beatmap_directory(osu_dir).unwrap_or_else(|| PathBuf::from(DEFAULT_SONGS_DIR));
+
+        Ok(Self::from_listing(&listing, osu_dir.join(songs_dir)))
+    }
+
+    /// Add a beatmap, replacing any previous path with the same hash.
+    pub fn insert(&mut self, hash: impl Into<String>, path: impl Into<PathBuf>) {
+        let mut hash = hash.into();
+        hash.make_ascii_lowercase();
+        self.paths.insert(hash, path.into());
+    }
+
+    /// Path of the beatmap with the hash.
+    pub fn get(&self, hash: &str) -> Option<&Path> {
+        self.paths
+            .get(&hash.to_ascii_lowercase())
+            .map(PathBuf::as_path)
+    }
+
+    /// Path of the beatmap that the replay was played on.
+    pub fn find(&self, replay: &Replay) -> Option<&Path> {
+        replay
+            .beatmap_hash
+            .as_deref()
+            .and_then(|hash| self.get(hash))
+    }
+
+    /// Amount of beatmaps in the index.
+    pub fn len(&self) -> usize {
+        self.paths.len()
+    }
+
+    /// Whether the index contains no beatmaps.
+    pub fn is_empty(&self) -> bool {
+        self.paths.is_empty()
+    }
+}
+
+/// The MD5 hash of a `.osu` file's content, as stored in [`Replay::beatmap_hash`].
+pub fn beatmap_hash(bytes: &[u8]) -> String {
+    format!("{:x}", Md5::digest(bytes))
+}
+
+/// Check that the content of a `.osu` file is the beatmap that the replay was played on.
+///
+/// Returns [`UrError::BeatmapMismatch`] if the hashes differ, e.g. because the map was edited
+/// or updated since. Replays without a beatmap hash can't be checked and always pass.
+pub fn verify_beatmap(bytes: &[u8], replay: &Replay) -> Result<(), UrError> {
+    let Some(expected) = replay.beatmap_hash.as_deref() else {
+        return Ok(());
+    };
+
+    let hash = beatmap_hash(bytes);
+
+    if hash.eq_ignore_ascii_case(expected) {
+        Ok(())
+    } else {
+        Err(UrError::BeatmapMismatch {
+            replay: expected.to_owned(),
+            map: hash,
+        })
+    }
+}
+
+/// The `BeatmapDirectory` of the user's config, `None` if there is none.
+///
+/// osu!stable names the config after the operating system's account rather than the player so
+/// every `osu!.<name>.cfg` is considered, most recently modified first. Relative directories
+/// are joined onto the osu! directory by the caller, absolute ones replace it.
+fn beatmap_directory(osu_dir: &Path) -> Option<PathBuf> {
+    let mut configs: Vec<_> = fs::read_dir(osu_dir)
+        .ok()?
+        .filter_map(|dir_entry| {
+            let dir_entry = dir_entry.ok()?;
+            let file_name = dir_entry.file_name();
+            let name = file_name.to_str()?;
+
+            let is_user_config = name
+                .strip_prefix("osu!.")
+                .and_then(|name| name.strip_suffix(".cfg"))
+                .is_some_and(|name| !name.is_empty());
+
+            let modified = dir_entry.metadata().and_then(|meta| meta.modified()).ok();
+
+            is_user_config.then(|| (modified, dir_entry.path()))
+        })
+        .collect();
+
+    configs.sort_unstable_by(|(a, _), (b, _)| b.cmp(a));
+
+    configs.into_iter().find_map(|(_, path)| {
+        let config = fs::read_to_string(path).ok()?;
+
+        config.lines().find_map(|line| {
+            let (key, value) = line.split_once('=')?;
+
+            (key.trim() == "BeatmapDirectory")
+                .then(|| value.trim())
+                .filter(|value| !value.is_empty())
+                .map(PathBuf::from)
+        })
+    })
+}
+
+pub(crate) fn find_files(dir: &Path, extension: &str) -> io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut dirs = vec![dir.to_owned()];
+
+    while let Some(dir) = dirs.pop() {
+        for dir_entry in fs::read_dir(dir)? {
+            let path = dir_entry?.path();
+
+            if path.is_dir() {
+                dirs.push(path);
+            } else if path
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case(extension))
+            {
+                files.push(path);
+            }
+        }
+    }
+
+    files.sort_unstable();
+
+    Ok(files)
+}
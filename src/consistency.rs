@@ -0,0 +1,197 @@
+use osu_db::{Mod, Mode, Replay};
+use rosu_pp::{Beatmap, BeatmapExt};
+
+use crate::{error_stats::ErrorStatistics, report::HitErrorReport};
+
+/// The size of a sliding window.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Window {
+    /// The given amount of consecutive hits.
+    Objects(usize),
+    /// Hits within the given amount of milliseconds of map time.
+    Duration(f64),
+}
+
+/// Where to split a map into sections.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SectionSplit {
+    /// A new section starts after each break.
+    #[default]
+    Breaks,
+    /// A new section starts at each uninherited timing point i.e. each change of BPM or meter.
+    TimingPoints,
+}
+
+/// Hit errors within a part of the map.
+#[derive(Clone, Debug)]
+pub struct UrSegment {
+    /// Start of the segment in ms of map time.
+    pub start_time: f64,
+    /// End of the segment in ms of map time.
+    pub end_time: f64,
+    /// Amount of hit errors within the segment.
+    pub n_hits: usize,
+    /// Statistics over the hit errors within the segment.
+    pub statistics: ErrorStatistics,
+}
+
+impl UrSegment {
+    /// Unstable rate of the hit errors within the segment.
+    pub fn unstable_rate(&self) -> f64 {
+        self.statistics.unstable_rate
+    }
+}
+
+/// How the hit errors of a [`HitErrorReport`] develop over the course of the map.
+#[derive(Clone, Debug, Default)]
+pub struct ConsistencyReport {
+    hits: Vec<TimedError>,
+    breaks: Vec<(f64, f64)>,
+    timing_points: Vec<f64>,
+}
+
+impl ConsistencyReport {
+    /// Place the report's hit errors at the start times of their objects.
+    ///
+    /// The report must have been calculated on the same map and replay.
+    pub fn new(map: &Beatmap, replay: &Replay, report: &HitErrorReport) -> Self {
+        let mods = replay
+            .mods
+            .without(Mod::DoubleTime)
+            .without(Mod::HalfTime)
+            .without(Mod::Nightcore);
+
+        // hit error indices refer to the objects of the calculation's mode
+        let start_times: Vec<_> = match replay.mode {
+            Mode::Standard | Mode::CatchTheBeat => map
+                .osu_hitobjects(mods.bits())
+                .iter()
+                .map(|h| h.start_time)
+                .collect(),
+            Mode::Taiko => map
+                .taiko_hitobjects()
+                .iter()
+                .map(|h| h.start_time)
+                .collect(),
+            Mode::Mania => map
+                .mania_hitobjects()
+                .iter()
+                .map(|h| h.start_time)
+                .collect(),
+        };
+
+        let mut hits: Vec<_> = report
+            .hit_errors
+            .iter()
+            .filter_map(|hit_error| {
+                start_times.get(hit_error.index).map(|&time| TimedError {
+                    time,
+                    error: hit_error.error,
+                })
+            })
+            .collect();
+
+        hits.sort_by(|a, b| a.time.total_cmp(&b.time));
+
+        let breaks = map
+            .breaks
+            .iter()
+            .map(|b| (b.start_time, b.end_time))
+            .collect();
+
+        let timing_points = map.timing_points.iter().map(|tp| tp.time).collect();
+
+        Self {
+            hits,
+            breaks,
+            timing_points,
+        }
+    }
+
+    /// Statistics over a window that slides across the hit errors, one segment per hit.
+    ///
+    /// Each window ends at a hit and reaches back the given amount of objects or milliseconds.
+    /// Windows over objects only start once enough hits were made, windows over durations
+    /// start at the first hit.
+    pub fn rolling(&self, window: Window) -> Vec<UrSegment> {
+        match window {
+            Window::Objects(0) => Vec::new(),
+            Window::Objects(n) => self.hits.windows(n).map(Self::segment).collect(),
+            Window::Duration(duration) => self
+                .hits
+                .iter()
+                .enumerate()
+                .map(|(i, hit)| {
+                    let start = self.hits[..=i].partition_point(|h| h.time <= hit.time - duration);
+
+                    Self::segment(&self.hits[start..=i])
+                })
+                .collect(),
+        }
+    }
+
+    /// Statistics over each section of the map.
+    ///
+    /// Sections without hit errors are left out.
+    pub fn sections(&self, split: SectionSplit) -> Vec<UrSegment> {
+        // gaps between sections; the first gap ends the first section
+        let gaps: Vec<(f64, f64)> = match split {
+            SectionSplit::Breaks => self.breaks.clone(),
+            SectionSplit::TimingPoints => self
+                .timing_points
+                .iter()
+                .skip(1)
+                .map(|&time| (time, time))
+                .collect(),
+        };
+
+        let mut segments = Vec::with_capacity(gaps.len() + 1);
+        let mut start = 0;
+
+        for i in 0..=gaps.len() {
+            let end = match gaps.get(i) {
+                Some(&(gap_start, _)) => {
+                    start + self.hits[start..].partition_point(|h| h.time < gap_start)
+                }
+                None => self.hits.len(),
+            };
+
+            let hits = &self.hits[start..end];
+
+            if !hits.is_empty() {
+                let mut segment = Self::segment(hits);
+
+                if let Some(&(_, prev_end)) = i.checked_sub(1).and_then(|i| gaps.get(i)) {
+                    segment.start_time = prev_end;
+                }
+
+                if let Some(&(gap_start, _)) = gaps.get(i) {
+                    segment.end_time = gap_start;
+                }
+
+                segments.push(segment);
+            }
+
+            start = end;
+        }
+
+        segments
+    }
+
+    fn segment(hits: &[TimedError]) -> UrSegment {
+        let errors: Vec<_> = hits.iter().map(|hit| hit.error).collect();
+
+        UrSegment {
+            start_time: hits.first().map_or(0.0, |hit| hit.time),
+            end_time: hits.last().map_or(0.0, |hit| hit.time),
+            n_hits: hits.len(),
+            statistics: ErrorStatistics::new(&errors),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+struct TimedError {
+    time: f64,
+    error: f64,
+}
@@ -0,0 +1,25 @@
+/// Interpolate between the values a setting has at difficulty 0, 5, and 10.
+///
+/// Hit windows, spinner requirements and the like scale with OD this way in every mode.
+pub(crate) fn difficulty_range(difficulty: f64, min: f64, mid: f64, max: f64) -> f64 {
+    if difficulty > 5.0 {
+        mid + (max - mid) * (difficulty - 5.0) / 5.0
+    } else if difficulty < 5.0 {
+        mid - (mid - min) * (5.0 - difficulty) / 5.0
+    } else {
+        mid
+    }
+}
+
+/// Radius of osu!standard circles in osu!pixels for the circle size after mods.
+///
+/// This is the radius that hit tests use, slightly enlarged like osu!stable does to make up
+/// for rounding errors in its gamefield scaling.
+pub(crate) fn circle_radius(cs: f64) -> f32 {
+    const BROKEN_GAMEFIELD_ROUNDING_ALLOWANCE: f32 = 1.00041;
+
+    let adjusted_cs = ((cs - 5.0) / 5.0) as f32;
+    let sprite_display_size = 512.0 / 8.0 * (1.0 - 0.7 * adjusted_cs);
+
+    sprite_display_size / 2.0 * BROKEN_GAMEFIELD_ROUNDING_ALLOWANCE
+}
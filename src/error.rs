@@ -0,0 +1,74 @@
+use std::{
+    error::Error as StdError,
+    fmt::{Display, Formatter, Result as FmtResult},
+};
+
+use osu_db::{Mode, Replay};
+use rosu_pp::{Beatmap, GameMode};
+
+/// Reasons why the unstable rate of a replay could not be calculated.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum UrError {
+    /// The replay does not contain any frames, e.g. because it was taken from a `scores.db`.
+    MissingReplayData,
+    /// Not a single object was hit so there are no hit errors.
+    NoHitsDetected,
+    /// The replay was not played in a mode that the calculation supports for the map.
+    ModeMismatch { replay: GameMode, map: GameMode },
+    /// The algorithm does not simulate judgements so they can't be validated.
+    JudgementsNotSimulated,
+    /// The MD5 hash of the map differs from the replay's beatmap hash so the replay was played
+    /// on another version of the map or on another map entirely.
+    BeatmapMismatch { replay: String, map: String },
+}
+
+impl UrError {
+    pub(crate) fn check_osu_mode(map: &Beatmap, replay: &Replay) -> Result<(), Self> {
+        Self::check_mode(map, replay, GameMode::Osu)
+    }
+
+    /// Ensure the replay was played in the given mode on a map of that mode or on an
+    /// osu!standard map that converts to it.
+    pub(crate) fn check_mode(map: &Beatmap, replay: &Replay, mode: GameMode) -> Result<(), Self> {
+        let replay_mode = game_mode(replay.mode);
+
+        if replay_mode != mode || (map.mode != mode && map.mode != GameMode::Osu) {
+            return Err(Self::ModeMismatch {
+                replay: replay_mode,
+                map: map.mode,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for UrError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::MissingReplayData => f.write_str("replay does not contain any replay data"),
+            Self::NoHitsDetected => f.write_str("no hits were detected in the replay"),
+            Self::ModeMismatch { replay, map } => {
+                write!(f, "cannot calculate a {replay:?} replay on a {map:?} map")
+            }
+            Self::JudgementsNotSimulated => {
+                f.write_str("the algorithm does not simulate judgements")
+            }
+            Self::BeatmapMismatch { replay, map } => write!(
+                f,
+                "the map's hash {map} does not match the replay's beatmap hash {replay}"
+            ),
+        }
+    }
+}
+
+impl StdError for UrError {}
+
+pub(crate) fn game_mode(mode: Mode) -> GameMode {
+    match mode {
+        Mode::Standard => GameMode::Osu,
+        Mode::Taiko => GameMode::Taiko,
+        Mode::CatchTheBeat => GameMode::Catch,
+        Mode::Mania => GameMode::Mania,
+    }
+}
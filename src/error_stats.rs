@@ -0,0 +1,160 @@
+/// Statistics over a list of hit errors.
+///
+/// The hit errors can come from any source, e.g. a [`HitErrorReport`](crate::HitErrorReport)
+/// or a list of integer offsets from another tool.
+#[derive(Clone, Debug, Default)]
+pub struct ErrorStatistics {
+    /// Average of all early hit errors, `0.0` if there were none.
+    pub minus_avg: f64,
+    /// Average of all late hit errors, `0.0` if there were none.
+    pub plus_avg: f64,
+    /// Earliest hit error, even if it was late, and `i32::MAX` if there were no hit errors.
+    pub minus_max: f64,
+    /// Latest hit error, `0.0` if there were no late hits.
+    pub plus_max: f64,
+    /// Average of all hit errors.
+    pub avg: f64,
+    /// Smallest hit error.
+    pub min: f64,
+    /// Largest hit error.
+    pub max: f64,
+    /// Median of all hit errors.
+    pub median: f64,
+    /// Standard error of the average i.e. the standard deviation divided by `sqrt(n)`.
+    pub standard_error: f64,
+    /// Skewness of the distribution; negative if the tail of early hits is longer.
+    pub skewness: f64,
+    /// Standard deviation of the hit errors, multiplied by 10.
+    pub unstable_rate: f64,
+    sorted: Vec<f64>,
+}
+
+impl ErrorStatistics {
+    pub fn new<T: Copy + Into<f64>>(hit_errors: &[T]) -> Self {
+        let mut total_plus: f64 = 0.0;
+        let mut total_minus: f64 = 0.0;
+        let mut total_all: f64 = 0.0;
+        let mut count_plus: usize = 0;
+        let mut count_minus: usize = 0;
+        let mut max: f64 = 0.0;
+        let mut min = f64::from(i32::MAX);
+
+        for hit_error in hit_errors.iter().map(|&n| n.into()) {
+            max = max.max(hit_error);
+            min = min.min(hit_error);
+
+            total_all += hit_error;
+
+            if hit_error >= 0.0 {
+                total_plus += hit_error;
+                count_plus += 1;
+            } else {
+                total_minus += hit_error;
+                count_minus += 1;
+            }
+        }
+
+        let len = hit_errors.len() as f64;
+        let avg = total_all / len;
+        let mut variance = 0.0;
+        let mut third_moment = 0.0;
+
+        for curr in hit_errors.iter().map(|&n| n.into()) {
+            let diff: f64 = curr - avg;
+            variance += diff * diff;
+            third_moment += diff * diff * diff;
+        }
+
+        variance /= len;
+        third_moment /= len;
+
+        let std_dev = variance.sqrt();
+
+        let minus_avg = if count_minus == 0 {
+            0.0
+        } else {
+            total_minus / count_minus as f64
+        };
+
+        let plus_avg = if count_plus == 0 {
+            0.0
+        } else {
+            total_plus / count_plus as f64
+        };
+
+        let mut sorted: Vec<f64> = hit_errors.iter().map(|&n| n.into()).collect();
+        sorted.sort_by(f64::total_cmp);
+
+        let skewness = if std_dev > 0.0 {
+            third_moment / (std_dev * std_dev * std_dev)
+        } else {
+            0.0
+        };
+
+        let mut this = Self {
+            minus_avg,
+            plus_avg,
+            minus_max: min,
+            plus_max: max,
+            avg,
+            min: sorted.first().copied().unwrap_or(f64::NAN),
+            max: sorted.last().copied().unwrap_or(f64::NAN),
+            median: f64::NAN,
+            standard_error: std_dev / len.sqrt(),
+            skewness,
+            unstable_rate: std_dev * 10.0,
+            sorted,
+        };
+
+        this.median = this.percentile(50.0);
+
+        this
+    }
+
+    /// The amount of hit errors.
+    pub fn len(&self) -> usize {
+        self.sorted.len()
+    }
+
+    /// Whether there were no hit errors.
+    pub fn is_empty(&self) -> bool {
+        self.sorted.is_empty()
+    }
+
+    /// The hit error below which `percentile`% of all hit errors fall.
+    ///
+    /// Values between two hit errors are linearly interpolated.
+    /// Returns `NaN` if there are no hit errors.
+    pub fn percentile(&self, percentile: f64) -> f64 {
+        let Some(last) = self.sorted.len().checked_sub(1) else {
+            return f64::NAN;
+        };
+
+        let rank = percentile.clamp(0.0, 100.0) / 100.0 * last as f64;
+        let lower = rank.floor() as usize;
+        let upper = rank.ceil() as usize;
+        let weight = rank - lower as f64;
+
+        self.sorted[lower] + (self.sorted[upper] - self.sorted[lower]) * weight
+    }
+
+    /// The unstable rate after ignoring the `percent`% of hit errors that are furthest from
+    /// the median.
+    pub fn trimmed_unstable_rate(&self, percent: f64) -> f64 {
+        let mut by_distance: Vec<_> = self
+            .sorted
+            .iter()
+            .map(|&hit_error| ((hit_error - self.median).abs(), hit_error))
+            .collect();
+
+        by_distance.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+
+        let ignored = (by_distance.len() as f64 * percent.clamp(0.0, 100.0) / 100.0) as usize;
+        let kept: Vec<_> = by_distance[..by_distance.len() - ignored]
+            .iter()
+            .map(|&(_, hit_error)| hit_error)
+            .collect();
+
+        Self::new(&kept).unstable_rate
+    }
+}
@@ -0,0 +1,435 @@
+use std::{
+    fmt::{Debug, Formatter, Result as FmtResult},
+    ops::{BitAnd, BitOr, Deref, Not},
+    slice::Iter,
+};
+
+use osu_db::{replay::Action, Replay};
+use rosu_pp::parse::Pos2;
+
+use crate::error::UrError;
+
+/// State of the buttons in a replay frame.
+///
+/// osu! sets M1 and M2 alongside K1 and K2 so pressing K1 sets both [`Buttons::K1`] and
+/// [`Buttons::M1`]. Use [`Buttons::distinct`] to tell keys and mouse buttons apart.
+#[derive(Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Buttons(u8);
+
+impl Buttons {
+    pub const NONE: Self = Self(0);
+    pub const M1: Self = Self(1 << 0);
+    pub const M2: Self = Self(1 << 1);
+    pub const K1: Self = Self(1 << 2);
+    pub const K2: Self = Self(1 << 3);
+    pub const SMOKE: Self = Self(1 << 4);
+
+    /// Buttons from the raw bits of a replay frame.
+    pub fn from_bits(bits: u8) -> Self {
+        Self(bits)
+    }
+
+    pub fn bits(self) -> u8 {
+        self.0
+    }
+
+    /// Both the buttons and the other buttons, same as `|` but usable in constants.
+    pub const fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Whether all of the other buttons are set.
+    pub fn contains(self, other: Self) -> bool {
+        (self.0 & other.0) == other.0
+    }
+
+    /// Whether any of the other buttons are set.
+    pub fn intersects(self, other: Self) -> bool {
+        (self.0 & other.0) > 0
+    }
+
+    pub fn m1(self) -> bool {
+        self.intersects(Self::M1)
+    }
+
+    pub fn m2(self) -> bool {
+        self.intersects(Self::M2)
+    }
+
+    pub fn k1(self) -> bool {
+        self.intersects(Self::K1)
+    }
+
+    pub fn k2(self) -> bool {
+        self.intersects(Self::K2)
+    }
+
+    pub fn smoke(self) -> bool {
+        self.intersects(Self::SMOKE)
+    }
+
+    /// Whether any button that can hit objects is set, i.e. anything but smoke.
+    ///
+    /// Keys usually set their mouse button as well but replays of older clients only set K1
+    /// or K2 so both are checked.
+    pub fn is_pressed(self) -> bool {
+        self.intersects(Self::M1 | Self::M2 | Self::K1 | Self::K2)
+    }
+
+    /// The [`Key`]s of the [`distinct`](Self::distinct) buttons, ordered as in [`Key::ALL`].
+    ///
+    /// Smoke is not a key and thus never included.
+    pub fn keys(self) -> impl Iterator<Item = Key> {
+        let distinct = self.distinct();
+
+        Key::ALL
+            .into_iter()
+            .filter(move |key| distinct.contains(key.button()))
+    }
+
+    /// The buttons without the mouse buttons that osu! sets along with K1 and K2.
+    pub fn distinct(self) -> Self {
+        let mut bits = self.0;
+
+        if self.k1() {
+            bits &= !Self::M1.0;
+        }
+
+        if self.k2() {
+            bits &= !Self::M2.0;
+        }
+
+        Self(bits)
+    }
+}
+
+impl BitOr for Buttons {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitAnd for Buttons {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        Self(self.0 & rhs.0)
+    }
+}
+
+impl Not for Buttons {
+    type Output = Self;
+
+    fn not(self) -> Self::Output {
+        Self(!self.0)
+    }
+}
+
+impl Debug for Buttons {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let names = [
+            (Self::M1, "M1"),
+            (Self::M2, "M2"),
+            (Self::K1, "K1"),
+            (Self::K2, "K2"),
+            (Self::SMOKE, "Smoke"),
+        ];
+
+        let mut list = f.debug_set();
+
+        for (button, name) in names {
+            if self.contains(button) {
+                list.entry(&format_args!("{name}"));
+            }
+        }
+
+        list.finish()
+    }
+}
+
+/// One of the buttons that can hit objects.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Key {
+    K1,
+    K2,
+    M1,
+    M2,
+}
+
+impl Key {
+    /// All keys in order of declaration.
+    pub const ALL: [Self; 4] = [Self::K1, Self::K2, Self::M1, Self::M2];
+
+    /// The [`Buttons`] of only this key.
+    pub fn button(self) -> Buttons {
+        match self {
+            Self::K1 => Buttons::K1,
+            Self::K2 => Buttons::K2,
+            Self::M1 => Buttons::M1,
+            Self::M2 => Buttons::M2,
+        }
+    }
+}
+
+/// A key being held down from its press until its release.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct KeyPress {
+    pub key: Key,
+    /// Time in ms at which the key was pressed.
+    pub press_time: i32,
+    /// Time in ms at which the key was released, `None` if it was still held when the replay
+    /// ended.
+    pub release_time: Option<i32>,
+}
+
+impl KeyPress {
+    /// How long the key was held in ms, `None` if it was never released.
+    pub fn hold_duration(&self) -> Option<i32> {
+        self.release_time
+            .map(|release_time| release_time - self.press_time)
+    }
+}
+
+/// A decoded replay frame.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ReplayFrame {
+    /// Time in ms since the start of the replay.
+    pub time: i32,
+    /// Cursor position in osu!standard, the catcher's x position in osu!catch, and the bitmask
+    /// of held columns in `pos.x` for osu!mania.
+    pub pos: Pos2,
+    /// All buttons that are held.
+    pub buttons: Buttons,
+    /// Buttons that were not held in the previous frame.
+    pub pressed: Buttons,
+    /// Buttons that were held in the previous frame but no longer are.
+    pub released: Buttons,
+}
+
+impl ReplayFrame {
+    /// The clicks of the frame.
+    ///
+    /// Pressing both sides within the same frame counts as two clicks, one for each key.
+    /// Smoke never hits objects so pressing only smoke is no click.
+    pub(crate) fn hit_frames(&self) -> impl Iterator<Item = HitFrame> {
+        let n_clicks = if !self.pressed.is_pressed() {
+            0
+        } else if self.pressed.intersects(Buttons::M1 | Buttons::K1)
+            && self.pressed.intersects(Buttons::M2 | Buttons::K2)
+        {
+            2
+        } else {
+            1
+        };
+
+        let (time, pos) = (self.time, self.pos);
+
+        self.pressed
+            .keys()
+            .take(n_clicks)
+            .map(move |key| HitFrame { time, pos, key })
+    }
+}
+
+/// The frames of a replay with absolute times.
+///
+/// Decoding follows osu!lazer's `LegacyScoreDecoder`:
+///
+/// - the frame with a delta of `-12345` holds the RNG seed instead of input
+/// - the two frames at `(256, -500)` at the start are skip markers and dropped
+/// - frames with a negative delta only advance the time and are dropped
+///
+/// Presses and releases are relative to the previous frame that was kept.
+#[derive(Clone, Debug, Default)]
+pub struct ReplayFrames {
+    frames: Vec<ReplayFrame>,
+    seed: Option<i32>,
+}
+
+impl ReplayFrames {
+    /// Decode the frames of the replay.
+    ///
+    /// Returns [`UrError::MissingReplayData`] if the replay has no frames.
+    pub fn from_replay(replay: &Replay) -> Result<Self, UrError> {
+        replay
+            .replay_data
+            .as_deref()
+            .map(Self::from_actions)
+            .ok_or(UrError::MissingReplayData)
+    }
+
+    /// Decode frames of raw replay actions.
+    pub fn from_actions(actions: &[Action]) -> Self {
+        let mut frames = Vec::with_capacity(actions.len());
+        let mut seed = None;
+        let mut decoder = FrameDecoder::default();
+
+        for action in actions {
+            match decoder.decode(action) {
+                Decoded::Frame(frame) => frames.push(frame),
+                Decoded::Seed(value) => seed = Some(value),
+                Decoded::Skipped => {}
+            }
+        }
+
+        Self { frames, seed }
+    }
+
+    /// The seed of the replay's random number generator, stored in its own frame since
+    /// version 20130319.
+    pub fn seed(&self) -> Option<i32> {
+        self.seed
+    }
+
+    pub fn iter(&self) -> Iter<'_, ReplayFrame> {
+        self.frames.iter()
+    }
+
+    /// Every press of each [`Key`] paired with its release, ordered by press time.
+    ///
+    /// Keys are told apart with [`Buttons::distinct`] so M1 and M2 only count while their key
+    /// is not held.
+    pub fn key_presses(&self) -> Vec<KeyPress> {
+        let mut presses = Vec::new();
+        let mut held: [Option<usize>; 4] = [None; 4];
+        let mut prev = Buttons::NONE;
+
+        for frame in self.frames.iter() {
+            let buttons = frame.buttons.distinct();
+
+            for (key, held) in Key::ALL.into_iter().zip(held.iter_mut()) {
+                let button = key.button();
+
+                if prev.contains(button) && !buttons.contains(button) {
+                    if let Some(i) = held.take() {
+                        presses[i] = KeyPress {
+                            release_time: Some(frame.time),
+                            ..presses[i]
+                        };
+                    }
+                } else if !prev.contains(button) && buttons.contains(button) {
+                    *held = Some(presses.len());
+
+                    presses.push(KeyPress {
+                        key,
+                        press_time: frame.time,
+                        release_time: None,
+                    });
+                }
+            }
+
+            prev = buttons;
+        }
+
+        presses
+    }
+
+    /// Frames in which any button was pressed, see [`ReplayFrame::hit_frames`].
+    pub(crate) fn hit_frames(&self) -> Vec<HitFrame> {
+        self.frames
+            .iter()
+            .flat_map(ReplayFrame::hit_frames)
+            .collect()
+    }
+}
+
+impl Deref for ReplayFrames {
+    type Target = [ReplayFrame];
+
+    fn deref(&self) -> &Self::Target {
+        &self.frames
+    }
+}
+
+impl<'f> IntoIterator for &'f ReplayFrames {
+    type Item = &'f ReplayFrame;
+    type IntoIter = Iter<'f, ReplayFrame>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.frames.iter()
+    }
+}
+
+impl IntoIterator for ReplayFrames {
+    type Item = ReplayFrame;
+    type IntoIter = std::vec::IntoIter<ReplayFrame>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.frames.into_iter()
+    }
+}
+
+/// Decodes replay actions one at a time, see [`ReplayFrames`].
+#[derive(Clone, Debug, Default)]
+pub(crate) struct FrameDecoder {
+    n_actions: usize,
+    time: i32,
+    prev_buttons: Buttons,
+}
+
+pub(crate) enum Decoded {
+    Frame(ReplayFrame),
+    Seed(i32),
+    Skipped,
+}
+
+impl FrameDecoder {
+    const SEED_DELTA: i64 = -12345;
+
+    pub(crate) fn decode(&mut self, action: &Action) -> Decoded {
+        let i = self.n_actions;
+        self.n_actions += 1;
+
+        if action.delta == Self::SEED_DELTA {
+            return Decoded::Seed(action.z as i32);
+        }
+
+        self.time += action.delta as i32;
+
+        // at the start of the replay, stable places two frames at time 0 and the skip
+        // boundary respectively
+        let is_skip = i < 2
+            && (action.x - 256.0).abs() <= f32::EPSILON
+            && (action.y + 500.0).abs() <= f32::EPSILON;
+
+        if is_skip || action.delta < 0 {
+            return Decoded::Skipped;
+        }
+
+        let buttons = Buttons::from_bits(action.z as u8);
+        let prev_buttons = self.prev_buttons;
+        self.prev_buttons = buttons;
+
+        Decoded::Frame(ReplayFrame {
+            time: self.time,
+            pos: Pos2 {
+                x: action.x,
+                y: action.y,
+            },
+            buttons,
+            pressed: buttons & !prev_buttons,
+            released: prev_buttons & !buttons,
+        })
+    }
+}
+
+/// A frame in which a button was pressed.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct HitFrame {
+    pub(crate) time: i32,
+    pub(crate) pos: Pos2,
+    /// The key that was pressed.
+    pub(crate) key: Key,
+}
+
+impl HitFrame {
+    pub(crate) fn dist_sq(&self, pos: Pos2) -> f32 {
+        (self.pos - pos).length_squared()
+    }
+}
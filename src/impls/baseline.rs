@@ -3,9 +3,36 @@ use std::collections::HashSet;
 use osu_db::{Mod, Replay};
 use rosu_pp::{Beatmap, BeatmapExt};
 
-use crate::models::{Buttons, ReplayData};
+use crate::{
+    algorithm::UrCalculator,
+    error::UrError,
+    frames::{Buttons, ReplayFrames},
+    options::UrOptions,
+    report::{HitError, HitErrorReport},
+};
+
+/// Straightforward search for a hit frame for each object.
+pub struct BaselineCalculator;
+
+impl UrCalculator for BaselineCalculator {
+    fn try_calculate_report_with(
+        &self,
+        map: &Beatmap,
+        replay: &Replay,
+        options: &UrOptions,
+    ) -> Result<HitErrorReport, UrError> {
+        try_calculate_report(map, replay)
+            .map(|report| report.with_time_scale(options.clock_rate(replay), options.time_scale))
+    }
+}
 
 pub fn calculate_ur(map: &Beatmap, replay: &Replay) -> f64 {
+    BaselineCalculator.calculate_ur(map, replay)
+}
+
+fn try_calculate_report(map: &Beatmap, replay: &Replay) -> Result<HitErrorReport, UrError> {
+    UrError::check_osu_mode(map, replay)?;
+
     let mods = replay
         .mods
         .without(Mod::DoubleTime)
@@ -13,22 +40,7 @@ pub fn calculate_ur(map: &Beatmap, replay: &Replay) -> f64 {
         .without(Mod::Nightcore)
         .bits();
 
-    let replay_data: Vec<_> = replay
-        .replay_data
-        .as_ref()
-        .unwrap()
-        .iter()
-        .scan(0, |time_elapsed, action| {
-            *time_elapsed += action.delta;
-
-            Some(ReplayData {
-                timestamp: *time_elapsed as f64,
-                x: action.x,
-                y: action.y,
-                keys: Buttons::from_f32(action.z),
-            })
-        })
-        .collect();
+    let replay_data = ReplayFrames::from_replay(replay)?;
 
     let attrs = map.attributes().mods(mods).build();
 
@@ -38,7 +50,7 @@ pub fn calculate_ur(map: &Beatmap, replay: &Replay) -> f64 {
     let hit_window_50 = 199.5 - attrs.od * 10.0;
     //let hit_window_50 = (150.0 + 50.0 * (5.0 - attrs.od) / 5.0) - 0.5;
 
-    let mut hit_errors: Vec<f64> = Vec::new();
+    let mut hit_errors: Vec<HitError> = Vec::new();
     let mut used_frames: HashSet<u64> = HashSet::new();
     let mut prev_hit = true;
 
@@ -52,51 +64,58 @@ pub fn calculate_ur(map: &Beatmap, replay: &Replay) -> f64 {
         for (j, frame) in replay_data.iter().enumerate() {
             let prev_frame_keys: Buttons = match j > 0 {
                 false => Buttons::default(),
-                true => replay_data[j - 1].keys,
+                true => replay_data[j - 1].buttons.distinct(),
             };
+            let timestamp = f64::from(frame.time);
+            let keys = frame.buttons.distinct();
             let latest_hit = match obj.is_slider() {
                 false => obj.start_time + hit_window_50,
                 true => (obj.start_time + hit_window_50).min(obj.end_time().round()),
             };
 
-            if frame.timestamp < obj.start_time - hit_window_50
-                || used_frames.contains(&frame.timestamp.to_bits())
+            if timestamp < obj.start_time - hit_window_50
+                || used_frames.contains(&timestamp.to_bits())
             {
                 continue;
-            } else if frame.timestamp > latest_hit {
+            } else if timestamp > latest_hit {
                 break;
             }
 
-            let in_circle = (frame.x - obj.stacked_pos().x) * (frame.x - obj.stacked_pos().x)
-                + (frame.y - obj.stacked_pos().y) * (frame.y - obj.stacked_pos().y)
+            let in_circle = (frame.pos.x - obj.stacked_pos().x)
+                * (frame.pos.x - obj.stacked_pos().x)
+                + (frame.pos.y - obj.stacked_pos().y) * (frame.pos.y - obj.stacked_pos().y)
                 < (radius * radius);
 
-            let m1 = frame.keys.m1() && !prev_frame_keys.m1();
-            let m2 = frame.keys.m2() && !prev_frame_keys.m2();
-            let k1 = frame.keys.k1() && !prev_frame_keys.k1();
-            let k2 = frame.keys.k2() && !prev_frame_keys.k2();
+            let m1 = keys.m1() && !prev_frame_keys.m1();
+            let m2 = keys.m2() && !prev_frame_keys.m2();
+            let k1 = keys.k1() && !prev_frame_keys.k1();
+            let k2 = keys.k2() && !prev_frame_keys.k2();
             let press = m1 || m2 || k1 || k2;
 
             let mut notelock = false;
             if i > 0 {
-                notelock =
-                    !prev_hit && frame.timestamp < hit_objects[i - 1].start_time + hit_window_50;
+                notelock = !prev_hit && timestamp < hit_objects[i - 1].start_time + hit_window_50;
 
                 if hit_objects[i - 1].is_slider() {
-                    let in_prev_cirle = (frame.x - hit_objects[i - 1].stacked_pos().x)
-                        * (frame.x - hit_objects[i - 1].stacked_pos().x)
-                        + (frame.y - hit_objects[i - 1].stacked_pos().y)
-                            * (frame.y - hit_objects[i - 1].stacked_pos().y)
+                    let in_prev_cirle = (frame.pos.x - hit_objects[i - 1].stacked_pos().x)
+                        * (frame.pos.x - hit_objects[i - 1].stacked_pos().x)
+                        + (frame.pos.y - hit_objects[i - 1].stacked_pos().y)
+                            * (frame.pos.y - hit_objects[i - 1].stacked_pos().y)
                         < (radius * radius);
                     let sliderlock =
-                        press && in_prev_cirle && frame.timestamp < hit_objects[i - 1].end_time();
+                        press && in_prev_cirle && timestamp < hit_objects[i - 1].end_time();
                     notelock = notelock || sliderlock;
                 }
             }
 
             if in_circle && press && !notelock {
-                hit_errors.push(frame.timestamp - obj.start_time);
-                used_frames.insert(frame.timestamp.to_bits());
+                hit_errors.push(HitError {
+                    index: i,
+                    error: timestamp - obj.start_time,
+                    key: (keys & !prev_frame_keys).keys().next(),
+                    pos: Some(frame.pos),
+                });
+                used_frames.insert(timestamp.to_bits());
                 hit = true;
                 break;
             }
@@ -104,15 +123,7 @@ pub fn calculate_ur(map: &Beatmap, replay: &Replay) -> f64 {
         prev_hit = hit;
     }
 
-    let sum: f64 = hit_errors.iter().sum();
-    let len = hit_errors.len() as f64;
-    let avg = sum / len;
-
-    let mut variance: f64 = 0.0;
-    for hit in hit_errors {
-        variance += (hit - avg) * (hit - avg);
-    }
-    variance /= len;
+    let n_spinners = hit_objects.iter().filter(|h| h.is_spinner()).count();
 
-    variance.sqrt() * 10.0
+    HitErrorReport::new(hit_errors, hit_objects.len(), n_spinners)
 }
@@ -0,0 +1,670 @@
+use osu_db::{Mod, Replay};
+use rosu_pp::{
+    catch::CatchObject,
+    osu::{NestedObjectKind, OsuObject, OsuObjectKind},
+    parse::HitObjectKind,
+    Beatmap, BeatmapExt, GameMode,
+};
+
+use crate::{
+    algorithm::UrCalculator,
+    error::UrError,
+    frames::ReplayFrames,
+    judgement::{CatchObjectKind, CatchResult, Judgement},
+    options::UrOptions,
+    report::HitErrorReport,
+};
+
+/// Catch analysis for osu!catch, including converted maps.
+///
+/// osu!catch judges by position rather than timing so the report has no hit errors and an
+/// unstable rate of `0.0`. Instead, it contains a [`CatchResult`] for each fruit and droplet
+/// and the amount of caught bananas. Bananas and tiny droplets are not judgements so they
+/// count as skipped. Tiny droplets are not simulated at all since their position depends on
+/// the slider's path, which means the 50s and katus in the replay's header can't be compared.
+pub struct CatchCalculator;
+
+impl UrCalculator for CatchCalculator {
+    fn try_calculate_report_with(
+        &self,
+        map: &Beatmap,
+        replay: &Replay,
+        options: &UrOptions,
+    ) -> Result<HitErrorReport, UrError> {
+        try_calculate_report(map, replay)
+            .map(|report| report.with_time_scale(options.clock_rate(replay), options.time_scale))
+    }
+}
+
+fn try_calculate_report(map: &Beatmap, replay: &Replay) -> Result<HitErrorReport, UrError> {
+    UrError::check_mode(map, replay, GameMode::Catch)?;
+
+    let mods = replay
+        .mods
+        .without(Mod::DoubleTime)
+        .without(Mod::HalfTime)
+        .without(Mod::Nightcore);
+
+    let cs = if mods.contains(Mod::HardRock) {
+        (map.cs * 1.3).min(10.0)
+    } else if mods.contains(Mod::Easy) {
+        map.cs / 2.0
+    } else {
+        map.cs
+    };
+
+    /*
+        public static float CalculateScale(IBeatmapDifficultyInfo difficulty)
+            => 1.0f - 0.7f * (difficulty.CircleSize - 5) / 5;
+
+        public static float CalculateCatchWidth(float scale)
+            => BASE_SIZE * Math.Abs(scale) * ALLOWED_CATCH_RANGE;
+    */
+
+    let scale = 1.0 - 0.7 * (f64::from(cs) - 5.0) / 5.0;
+    let half_catch_width = CATCHER_SIZE * scale.abs() * ALLOWED_CATCH_RANGE / 2.0;
+
+    let frames = CatcherFrames::from_replay(replay)?;
+    let catch_objects = map.catch_hitobjects(mods.bits());
+    let osu_objects = map.osu_hitobjects(mods.bits());
+
+    let conversions = convert(&osu_objects, &catch_objects);
+    let kinds = object_kinds(&conversions, &catch_objects);
+    let random_objects = RandomObjects::new(
+        map,
+        &conversions,
+        &catch_objects,
+        mods.contains(Mod::HardRock),
+    );
+
+    let catch_at = |time: f64, pos: f32| {
+        let (x, dashing) = frames.catcher_at(time);
+        let offset = f64::from(pos - x);
+
+        (offset, dashing, offset.abs() <= half_catch_width)
+    };
+
+    let catches: Vec<_> = catch_objects
+        .iter()
+        .zip(kinds)
+        .enumerate()
+        .map(|(index, (h, kind))| {
+            let (offset, dashing, caught) = catch_at(h.time, h.pos);
+
+            CatchResult {
+                index,
+                kind,
+                caught,
+                offset,
+                dashing,
+            }
+        })
+        .collect();
+
+    let judgements = catches
+        .iter()
+        .map(|catch| Judgement {
+            index: catch.index,
+            result: catch.result(),
+        })
+        .collect();
+
+    let n_bananas = random_objects
+        .bananas
+        .iter()
+        .filter(|banana| catch_at(banana.time, banana.x).2)
+        .count();
+
+    let n_caught = catches.iter().filter(|catch| catch.caught).count();
+    let n_missed = catches.len() - n_caught;
+    let n_skipped = random_objects.bananas.len() + random_objects.n_tiny_droplets;
+
+    HitErrorReport::without_hit_errors(n_caught, n_skipped, n_missed).map(|report| {
+        report
+            .with_judgements(judgements)
+            .with_catches(catches, n_bananas)
+    })
+}
+
+const CATCHER_SIZE: f64 = 106.75;
+const ALLOWED_CATCH_RANGE: f64 = 0.8;
+
+/// Nested objects whose time is this close to a slider's head, repeat, or tail are fruits.
+const FRUIT_TIME_LENIENCE: f64 = 1.0;
+
+/// The catch objects an osu!standard object turns into.
+enum Conversion {
+    /// Circles turn into a single fruit.
+    Fruit,
+    /// Sliders turn into fruits at their head, repeats, and tail with droplets in between.
+    JuiceStream(Vec<CatchObjectKind>),
+    /// Spinners turn into bananas which are not part of the catch objects.
+    BananaShower { start_time: f64, end_time: f64 },
+}
+
+/// Convert each osu!standard object based on the catch objects it turned into.
+fn convert(osu_objects: &[OsuObject], catch_objects: &[CatchObject]) -> Vec<Conversion> {
+    let mut conversions = Vec::with_capacity(osu_objects.len());
+    let mut times = catch_objects.iter().map(|h| h.time);
+
+    for h in osu_objects {
+        let slider = match h.kind {
+            OsuObjectKind::Slider(ref slider) => slider,
+            OsuObjectKind::Spinner { end_time } => {
+                conversions.push(Conversion::BananaShower {
+                    start_time: h.start_time,
+                    end_time,
+                });
+
+                continue;
+            }
+            _ => {
+                if times.next().is_none() {
+                    break;
+                }
+
+                conversions.push(Conversion::Fruit);
+
+                continue;
+            }
+        };
+
+        let is_fruit_time = |time: f64| {
+            let is_repeat = slider.nested_objects.iter().any(|nested| {
+                nested.kind == NestedObjectKind::Repeat
+                    && (nested.start_time - time).abs() <= FRUIT_TIME_LENIENCE
+            });
+
+            is_repeat || (slider.end_time - time).abs() <= FRUIT_TIME_LENIENCE
+        };
+
+        // slider head
+        if times.next().is_none() {
+            break;
+        }
+
+        let mut kinds = vec![CatchObjectKind::Fruit];
+
+        // everything up to and including the tail
+        for time in times.by_ref() {
+            if is_fruit_time(time) {
+                kinds.push(CatchObjectKind::Fruit);
+            } else {
+                kinds.push(CatchObjectKind::Droplet);
+            }
+
+            if (slider.end_time - time).abs() <= FRUIT_TIME_LENIENCE {
+                break;
+            }
+        }
+
+        conversions.push(Conversion::JuiceStream(kinds));
+    }
+
+    conversions
+}
+
+/// Kind of each catch object, ordered like the catch objects.
+fn object_kinds(conversions: &[Conversion], catch_objects: &[CatchObject]) -> Vec<CatchObjectKind> {
+    let mut kinds: Vec<_> = conversions
+        .iter()
+        .flat_map(|conversion| match conversion {
+            Conversion::Fruit => &[CatchObjectKind::Fruit],
+            Conversion::JuiceStream(kinds) => kinds.as_slice(),
+            Conversion::BananaShower { .. } => &[],
+        })
+        .copied()
+        .collect();
+
+    kinds.resize(catch_objects.len(), CatchObjectKind::Fruit);
+
+    kinds
+}
+
+/// A banana as positioned by osu!stable's random number generator.
+struct Banana {
+    time: f64,
+    x: f32,
+}
+
+/// Objects of the converted map that are not part of the catch objects.
+///
+/// Bananas and tiny droplets are positioned by a random number generator with a fixed seed
+/// which the droplets and HardRock's fruit offsets also draw from. Replaying every draw in
+/// order places the bananas exactly like osu!stable. Tiny droplets would also need the
+/// slider's path to be positioned so they are only counted.
+struct RandomObjects {
+    bananas: Vec<Banana>,
+    n_tiny_droplets: usize,
+}
+
+/*
+    var rng = new LegacyRandom(RNG_SEED);
+
+    float? lastPosition = null;
+    double lastStartTime = 0;
+
+    foreach (var obj in beatmap.HitObjects.OfType<CatchHitObject>())
+    {
+        switch (obj)
+        {
+            case Fruit fruit:
+                if (hardRockOffsets)
+                    applyHardRockOffset(fruit, ref lastPosition, ref lastStartTime, rng);
+                break;
+
+            case BananaShower bananaShower:
+                foreach (var banana in bananaShower.NestedHitObjects.OfType<Banana>())
+                {
+                    banana.XOffset = (float)(rng.NextDouble() * CatchPlayfield.WIDTH);
+                    rng.Next(); // osu!stable retrieved a random banana type
+                    rng.Next(); // osu!stable retrieved a random banana rotation
+                    rng.Next(); // osu!stable retrieved a random banana colour
+                }
+                break;
+
+            case JuiceStream juiceStream:
+                lastPosition = juiceStream.OriginalX + juiceStream.Path.ControlPoints[^1].Position.X;
+                lastStartTime = juiceStream.StartTime;
+
+                foreach (var nested in juiceStream.NestedHitObjects)
+                {
+                    if (catchObject is TinyDroplet)
+                        catchObject.XOffset = Math.Clamp(rng.Next(-20, 20), ...);
+                    else if (catchObject is Droplet)
+                        rng.Next(); // osu!stable retrieved a random droplet rotation
+                }
+                break;
+        }
+    }
+*/
+
+impl RandomObjects {
+    fn new(
+        map: &Beatmap,
+        conversions: &[Conversion],
+        catch_objects: &[CatchObject],
+        hard_rock: bool,
+    ) -> Self {
+        let mut rng = LegacyRandom::new(RNG_SEED);
+        let mut offsets = HardRockOffsets::default();
+        let mut bananas = Vec::new();
+        let mut n_tiny_droplets = 0;
+        let mut catch_objects = catch_objects.iter();
+
+        for (h, conversion) in map.hit_objects.iter().zip(conversions) {
+            match conversion {
+                Conversion::Fruit => {
+                    catch_objects.next();
+
+                    if hard_rock {
+                        offsets.apply(h.pos.x, h.start_time, &mut rng);
+                    }
+                }
+                Conversion::BananaShower {
+                    start_time,
+                    end_time,
+                } => {
+                    for time in banana_times(*start_time, *end_time) {
+                        let x = (rng.next_double() * f64::from(PLAYFIELD_WIDTH)) as f32;
+
+                        // banana type, rotation, and colour
+                        rng.next();
+                        rng.next();
+                        rng.next();
+
+                        bananas.push(Banana { time, x });
+                    }
+                }
+                Conversion::JuiceStream(kinds) => {
+                    if let HitObjectKind::Slider {
+                        ref control_points, ..
+                    } = h.kind
+                    {
+                        let last_x = control_points.last().map_or(0.0, |point| point.pos.x);
+                        offsets.last_position = Some(h.pos.x + last_x);
+                        offsets.last_start_time = h.start_time;
+                    }
+
+                    let times: Vec<_> = catch_objects
+                        .by_ref()
+                        .take(kinds.len())
+                        .map(|h| h.time)
+                        .collect();
+
+                    let n_droplets = kinds
+                        .iter()
+                        .filter(|kind| **kind == CatchObjectKind::Droplet)
+                        .count();
+
+                    let n_tiny = tiny_droplet_count(&times);
+
+                    // a random offset for each tiny droplet and a random rotation for each droplet
+                    for _ in 0..n_droplets + n_tiny {
+                        rng.next();
+                    }
+
+                    n_tiny_droplets += n_tiny;
+                }
+            }
+        }
+
+        Self {
+            bananas,
+            n_tiny_droplets,
+        }
+    }
+}
+
+const RNG_SEED: i32 = 1337;
+
+/*
+    double spacing = Duration;
+    while (spacing > 100)
+        spacing /= 2;
+
+    if (spacing <= 0)
+        return;
+
+    double time = StartTime;
+
+    while (time <= EndTime)
+    {
+        AddNested(new Banana { StartTime = time, ... });
+        time += spacing;
+    }
+*/
+
+fn banana_times(start_time: f64, end_time: f64) -> Vec<f64> {
+    let mut spacing = end_time - start_time;
+
+    while spacing > 100.0 {
+        spacing /= 2.0;
+    }
+
+    if spacing <= 0.0 {
+        return Vec::new();
+    }
+
+    let mut time = start_time;
+    let mut times = Vec::new();
+
+    while time <= end_time {
+        times.push(time);
+        time += spacing;
+    }
+
+    times
+}
+
+/*
+    if (lastEvent != null)
+    {
+        double sinceLastTick = (int)e.Time - (int)lastEvent.Value.Time;
+
+        if (sinceLastTick > 80)
+        {
+            double timeBetweenTiny = sinceLastTick;
+            while (timeBetweenTiny > 100)
+                timeBetweenTiny /= 2;
+
+            for (double t = timeBetweenTiny; t < sinceLastTick; t += timeBetweenTiny)
+            {
+                AddNested(new TinyDroplet { ... });
+            }
+        }
+    }
+
+    // this also includes LegacyLastTick and this is used for TinyDroplet generation above.
+    lastEvent = e;
+*/
+
+/// Amount of tiny droplets between the fruits and droplets of a juice stream.
+///
+/// The slider's legacy last tick sits between the last droplet and the tail so it takes part
+/// even though it is neither a fruit nor a droplet. Gaps are measured in whole milliseconds
+/// like osu!stable which can result in fewer tiny droplets than rosu-pp counts.
+fn tiny_droplet_count(times: &[f64]) -> usize {
+    let [head, .., tail] = *times else {
+        return 0;
+    };
+
+    let legacy_last_tick = (head + (tail - head) / 2.0).max(tail - LEGACY_LAST_TICK_OFFSET);
+
+    let events = times[..times.len() - 1]
+        .iter()
+        .copied()
+        .chain([legacy_last_tick, tail]);
+
+    let mut count = 0;
+
+    for (prev, curr) in events.clone().zip(events.skip(1)) {
+        let since_last_tick = f64::from(curr as i32 - prev as i32);
+
+        if since_last_tick <= 80.0 {
+            continue;
+        }
+
+        let mut time_between_tiny = since_last_tick;
+
+        while time_between_tiny > 100.0 {
+            time_between_tiny /= 2.0;
+        }
+
+        let mut t = time_between_tiny;
+
+        while t < since_last_tick {
+            count += 1;
+            t += time_between_tiny;
+        }
+    }
+
+    count
+}
+
+const LEGACY_LAST_TICK_OFFSET: f64 = 36.0;
+
+/// HardRock's offsets of fruits relative to the previous fruit.
+///
+/// The fruits themselves are already offset during the conversion so this only keeps track of
+/// the random numbers drawn along the way.
+#[derive(Default)]
+struct HardRockOffsets {
+    last_position: Option<f32>,
+    last_start_time: f64,
+}
+
+/*
+    float offsetPosition = hitObject.OriginalX;
+    double startTime = hitObject.StartTime;
+
+    if (lastPosition == null || lastPosition == 0)
+    {
+        lastPosition = offsetPosition;
+        lastStartTime = startTime;
+        return;
+    }
+
+    float positionDiff = offsetPosition - lastPosition.Value;
+    int timeDiff = (int)(startTime - lastStartTime);
+
+    if (timeDiff > 1000)
+    {
+        lastPosition = offsetPosition;
+        lastStartTime = startTime;
+        return;
+    }
+
+    if (positionDiff == 0)
+    {
+        applyRandomOffset(ref offsetPosition, timeDiff / 4d, rng);
+        hitObject.XOffset = offsetPosition - hitObject.X;
+        return;
+    }
+
+    if (Math.Abs(positionDiff) < timeDiff / 3)
+        applyOffset(ref offsetPosition, positionDiff);
+
+    lastPosition = offsetPosition;
+    lastStartTime = startTime;
+*/
+
+impl HardRockOffsets {
+    fn apply(&mut self, x: f32, start_time: f64, rng: &mut LegacyRandom) {
+        let mut position = x;
+
+        let Some(last_position) = self.last_position.filter(|last| *last != 0.0) else {
+            self.last_position = Some(position);
+            self.last_start_time = start_time;
+
+            return;
+        };
+
+        let position_diff = position - last_position;
+        let time_diff = (start_time - self.last_start_time) as i32;
+
+        if time_diff > 1000 {
+            self.last_position = Some(position);
+            self.last_start_time = start_time;
+
+            return;
+        }
+
+        if position_diff == 0.0 {
+            // the randomly offset position is not remembered so only the draws matter
+            rng.next_bool();
+            rng.next_double();
+
+            return;
+        }
+
+        if position_diff.abs() < (time_diff / 3) as f32 {
+            if position_diff > 0.0 {
+                if position + position_diff < PLAYFIELD_WIDTH {
+                    position += position_diff;
+                }
+            } else if position + position_diff > 0.0 {
+                position += position_diff;
+            }
+        }
+
+        self.last_position = Some(position);
+        self.last_start_time = start_time;
+    }
+}
+
+/*
+    public uint NextUInt()
+    {
+        uint t = X ^ (X << 11);
+        X = Y;
+        Y = Z;
+        Z = W;
+        return W = W ^ (W >> 19) ^ t ^ (t >> 8);
+    }
+*/
+
+/// osu!stable's xorshift random number generator.
+struct LegacyRandom {
+    x: u32,
+    y: u32,
+    z: u32,
+    w: u32,
+    bit_buffer: u32,
+    bit_index: u32,
+}
+
+impl LegacyRandom {
+    fn new(seed: i32) -> Self {
+        Self {
+            x: seed as u32,
+            y: 842_502_087,
+            z: 3_579_807_591,
+            w: 273_326_509,
+            bit_buffer: 0,
+            bit_index: 32,
+        }
+    }
+
+    fn next_uint(&mut self) -> u32 {
+        let t = self.x ^ (self.x << 11);
+        self.x = self.y;
+        self.y = self.z;
+        self.z = self.w;
+        self.w = self.w ^ (self.w >> 19) ^ t ^ (t >> 8);
+
+        self.w
+    }
+
+    fn next(&mut self) -> i32 {
+        (self.next_uint() & 0x7F_FF_FF_FF) as i32
+    }
+
+    fn next_double(&mut self) -> f64 {
+        f64::from(self.next()) / (f64::from(i32::MAX) + 1.0)
+    }
+
+    /// Draws a new number only for every 32nd bool.
+    fn next_bool(&mut self) -> bool {
+        if self.bit_index == 32 {
+            self.bit_buffer = self.next_uint();
+            self.bit_index = 1;
+        } else {
+            self.bit_index += 1;
+            self.bit_buffer >>= 1;
+        }
+
+        self.bit_buffer & 1 == 1
+    }
+}
+
+/// Position of the catcher as recorded in each frame.
+struct CatcherFrames {
+    frames: Vec<CatcherFrame>,
+}
+
+struct CatcherFrame {
+    time: f64,
+    x: f32,
+    dashing: bool,
+}
+
+impl CatcherFrames {
+    fn from_replay(replay: &Replay) -> Result<Self, UrError> {
+        let frames = ReplayFrames::from_replay(replay)?
+            .iter()
+            .map(|frame| CatcherFrame {
+                time: f64::from(frame.time),
+                // the catcher's x position is stored in x while y is unused
+                x: frame.pos.x,
+                // dashing is stored as M1
+                dashing: frame.buttons.m1(),
+            })
+            .collect();
+
+        Ok(Self { frames })
+    }
+
+    /// Position and dash state of the catcher at the given time.
+    ///
+    /// The position is interpolated between the surrounding frames.
+    fn catcher_at(&self, time: f64) -> (f32, bool) {
+        let i = self.frames.partition_point(|frame| frame.time <= time);
+
+        match (
+            i.checked_sub(1).map(|i| &self.frames[i]),
+            self.frames.get(i),
+        ) {
+            (Some(prev), Some(next)) if next.time > prev.time => {
+                let t = ((time - prev.time) / (next.time - prev.time)) as f32;
+
+                (prev.x + (next.x - prev.x) * t, prev.dashing)
+            }
+            (Some(prev), _) => (prev.x, prev.dashing),
+            (None, Some(next)) => (next.x, next.dashing),
+            (None, None) => (PLAYFIELD_WIDTH / 2.0, false),
+        }
+    }
+}
+
+const PLAYFIELD_WIDTH: f32 = 512.0;
@@ -1,13 +1,40 @@
 use osu_db::Replay;
 use rosu_pp::{osu::OsuObjectKind, Beatmap, BeatmapExt};
 
-use self::{error_stats::ErrorStatistics, frames::HitFrames};
-
-mod error_stats;
-mod frames;
+use crate::{
+    algorithm::UrCalculator,
+    error::UrError,
+    frames::ReplayFrames,
+    options::UrOptions,
+    report::{HitError, HitErrorReport},
+};
+
+/// Port of circleguard's hit detection.
+pub struct CircleguardCalculator;
+
+impl UrCalculator for CircleguardCalculator {
+    fn try_calculate_report_with(
+        &self,
+        map: &Beatmap,
+        replay: &Replay,
+        options: &UrOptions,
+    ) -> Result<HitErrorReport, UrError> {
+        try_calculate_report(map, replay, options)
+            .map(|report| report.with_time_scale(options.clock_rate(replay), options.time_scale))
+    }
+}
 
-#[cfg_attr(not(feature = "circleguard"), allow(unused))]
 pub fn calculate_ur(map: &Beatmap, replay: &Replay) -> f64 {
+    CircleguardCalculator.calculate_ur(map, replay)
+}
+
+fn try_calculate_report(
+    map: &Beatmap,
+    replay: &Replay,
+    options: &UrOptions,
+) -> Result<HitErrorReport, UrError> {
+    UrError::check_osu_mode(map, replay)?;
+
     let mods = replay.mods.bits() & !(NC | HT);
 
     let mut od = map.od as f64;
@@ -24,14 +51,14 @@ pub fn calculate_ur(map: &Beatmap, replay: &Replay) -> f64 {
     let hw_50 = hit_window_50(od);
     let radius_sq = radius_sq(cs);
 
-    let frames = HitFrames::from_replay(replay);
+    let frames = ReplayFrames::from_replay(replay)?.hit_frames();
     let hit_objects = map.osu_hitobjects(mods);
     let mut hit_errors = Vec::with_capacity(hit_objects.len());
 
     let mut hitobj_i = 0;
     let mut frame_i = 0;
 
-    let sliderbug_fixed = true; // TODO
+    let sliderbug_fixed = options.sliderbug_fixed(replay);
 
     while hitobj_i < hit_objects.len() && frame_i < frames.len() {
         let hitobj = &hit_objects[hitobj_i];
@@ -41,7 +68,7 @@ pub fn calculate_ur(map: &Beatmap, replay: &Replay) -> f64 {
 
         let hitobj_end_time = match hitobj.kind {
             OsuObjectKind::Circle => hitobj_t + hw_50,
-            OsuObjectKind::Slider(ref slider) => slider.end_time as i32 + hw_50,
+            OsuObjectKind::Slider(ref slider) => slider.end_time as i32,
             OsuObjectKind::Spinner { end_time } => end_time as i32,
         };
 
@@ -70,7 +97,7 @@ pub fn calculate_ur(map: &Beatmap, replay: &Replay) -> f64 {
         }
 
         if frame.time <= hitobj_t - hw_50 {
-            if frame.pos.dist_sq(hitobj.pos) <= radius_sq && !hitobj.is_spinner() {
+            if frame.dist_sq(hitobj.pos) <= radius_sq && !hitobj.is_spinner() {
                 if hitobj.is_slider() && sliderbug_fixed {
                     while frames[frame_i].time < notelock_end_time {
                         frame_i += 1;
@@ -90,10 +117,15 @@ pub fn calculate_ur(map: &Beatmap, replay: &Replay) -> f64 {
         } else if frame.time >= notelock_end_time {
             hitobj_i += 1;
         } else if frame.time < hitobj_t + hw_50
-            && frame.pos.dist_sq(hitobj.pos) <= radius_sq
+            && frame.dist_sq(hitobj.pos) <= radius_sq
             && !hitobj.is_spinner()
         {
-            hit_errors.push(frame.time - hitobj_t);
+            hit_errors.push(HitError {
+                index: hitobj_i,
+                error: (frame.time - hitobj_t) as f64,
+                key: Some(frame.key),
+                pos: Some(frame.pos),
+            });
 
             if hitobj.is_slider() && sliderbug_fixed {
                 while frames[frame_i].time < notelock_end_time {
@@ -113,9 +145,9 @@ pub fn calculate_ur(map: &Beatmap, replay: &Replay) -> f64 {
         }
     }
 
-    let stats = ErrorStatistics::new(&hit_errors);
+    let n_spinners = hit_objects.iter().filter(|h| h.is_spinner()).count();
 
-    stats.unstable_rate
+    HitErrorReport::new(hit_errors, hit_objects.len(), n_spinners)
 }
 
 const HIT_WINDOW_MISS: i32 = 400;
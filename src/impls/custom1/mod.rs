@@ -1,14 +1,40 @@
 use osu_db::Replay;
 use rosu_pp::{Beatmap, BeatmapExt};
 
-use self::{error_stats::ErrorStatistics, frames::HitFrames, hit_object::HitObject};
+use crate::{
+    algorithm::UrCalculator,
+    error::UrError,
+    frames::ReplayFrames,
+    options::UrOptions,
+    report::{HitError, HitErrorReport},
+};
+
+use self::hit_object::HitObject;
 
-mod error_stats;
-mod frames;
 mod hit_object;
 
-#[cfg_attr(not(feature = "custom1"), allow(unused))]
+/// Frame-driven search for the hit object of each click.
+pub struct Custom1Calculator;
+
+impl UrCalculator for Custom1Calculator {
+    fn try_calculate_report_with(
+        &self,
+        map: &Beatmap,
+        replay: &Replay,
+        options: &UrOptions,
+    ) -> Result<HitErrorReport, UrError> {
+        try_calculate_report(map, replay)
+            .map(|report| report.with_time_scale(options.clock_rate(replay), options.time_scale))
+    }
+}
+
 pub fn calculate_ur(map: &Beatmap, replay: &Replay) -> f64 {
+    Custom1Calculator.calculate_ur(map, replay)
+}
+
+fn try_calculate_report(map: &Beatmap, replay: &Replay) -> Result<HitErrorReport, UrError> {
+    UrError::check_osu_mode(map, replay)?;
+
     let mods = replay.mods.bits() & !(NC | HT);
 
     let mut od = map.od as f64;
@@ -25,7 +51,7 @@ pub fn calculate_ur(map: &Beatmap, replay: &Replay) -> f64 {
     let hw_50 = hit_window_50(od);
     let radius_sq = radius_sq(cs);
 
-    let frames = HitFrames::from_replay(replay);
+    let frames = ReplayFrames::from_replay(replay)?.hit_frames();
     let hit_objects = map.osu_hitobjects(mods);
     let mut hit_objects: Vec<_> = hit_objects.iter().map(|h| HitObject::new(h)).collect();
     let mut hit_errors = Vec::with_capacity(hit_objects.len());
@@ -42,7 +68,7 @@ pub fn calculate_ur(map: &Beatmap, replay: &Replay) -> f64 {
         let h_opt = hit_objects[start_idx..end_idx]
             .iter()
             .zip(start_idx..)
-            .find(|(h, _)| !h.found_hit && frame.pos.dist_sq(h.pos()) <= radius_sq && !h.ignore());
+            .find(|(h, _)| !h.found_hit && frame.dist_sq(h.pos()) <= radius_sq && !h.ignore());
 
         let Some((h, i)) = h_opt else { continue };
 
@@ -60,13 +86,18 @@ pub fn calculate_ur(map: &Beatmap, replay: &Replay) -> f64 {
             }
         }
 
-        hit_errors.push(frame.time - h.start_time());
+        hit_errors.push(HitError {
+            index: i,
+            error: (frame.time - h.start_time()) as f64,
+            key: Some(frame.key),
+            pos: Some(frame.pos),
+        });
         hit_objects[i].found_hit = true;
     }
 
-    let stats = ErrorStatistics::new(&hit_errors);
+    let n_ignored = hit_objects.iter().filter(|h| h.ignore()).count();
 
-    stats.unstable_rate
+    HitErrorReport::new(hit_errors, hit_objects.len(), n_ignored)
 }
 
 const EZ: u32 = 1 << 1;
@@ -1,13 +1,36 @@
 use osu_db::Replay;
 use rosu_pp::{osu::OsuObjectKind, Beatmap, BeatmapExt};
 
-use self::{error_stats::ErrorStatistics, frames::HitFrames};
-
-mod error_stats;
-mod frames;
+use crate::{
+    algorithm::UrCalculator,
+    error::UrError,
+    frames::ReplayFrames,
+    options::UrOptions,
+    report::{HitError, HitErrorReport},
+};
+
+/// Object-driven search for the click of each hit object.
+pub struct Custom2Calculator;
+
+impl UrCalculator for Custom2Calculator {
+    fn try_calculate_report_with(
+        &self,
+        map: &Beatmap,
+        replay: &Replay,
+        options: &UrOptions,
+    ) -> Result<HitErrorReport, UrError> {
+        try_calculate_report(map, replay)
+            .map(|report| report.with_time_scale(options.clock_rate(replay), options.time_scale))
+    }
+}
 
-#[cfg_attr(not(feature = "custom2"), allow(unused))]
 pub fn calculate_ur(map: &Beatmap, replay: &Replay) -> f64 {
+    Custom2Calculator.calculate_ur(map, replay)
+}
+
+fn try_calculate_report(map: &Beatmap, replay: &Replay) -> Result<HitErrorReport, UrError> {
+    UrError::check_osu_mode(map, replay)?;
+
     let mods = replay.mods.bits() & !(NC | HT);
 
     let mut od = map.od as f64;
@@ -27,7 +50,7 @@ pub fn calculate_ur(map: &Beatmap, replay: &Replay) -> f64 {
     let hit_objects = map.osu_hitobjects(mods);
     let mut hit_errors = Vec::with_capacity(hit_objects.len());
 
-    let frames = HitFrames::from_replay(replay);
+    let frames = ReplayFrames::from_replay(replay)?.hit_frames();
     let mut frames = frames.as_slice();
 
     for i in 0..hit_objects.len() {
@@ -60,13 +83,18 @@ pub fn calculate_ur(map: &Beatmap, replay: &Replay) -> f64 {
             .iter()
             .zip(start_idx..)
             .find(|(frame, _)| {
-                frame.pos.dist_sq(pos) <= radius_sq
+                frame.dist_sq(pos) <= radius_sq
                     && prev_end_time.map_or(true, |prev_end_time| prev_end_time < frame.time)
             });
 
         let next_start = match frame_opt {
             Some((frame, j)) => {
-                hit_errors.push(frame.time - start_time);
+                hit_errors.push(HitError {
+                    index: i,
+                    error: (frame.time - start_time) as f64,
+                    key: Some(frame.key),
+                    pos: Some(frame.pos),
+                });
 
                 j + 1
             }
@@ -77,9 +105,9 @@ pub fn calculate_ur(map: &Beatmap, replay: &Replay) -> f64 {
         frames = &frames[next_start..];
     }
 
-    let stats = ErrorStatistics::new(&hit_errors);
+    let n_spinners = hit_objects.iter().filter(|h| h.is_spinner()).count();
 
-    stats.unstable_rate
+    HitErrorReport::new(hit_errors, hit_objects.len(), n_spinners)
 }
 
 const EZ: u32 = 1 << 1;
@@ -3,10 +3,36 @@ use std::{collections::HashSet, iter};
 use osu_db::{Mod, Replay};
 use rosu_pp::{osu::OsuObject, Beatmap, BeatmapExt};
 
-use crate::models::{Buttons, ReplayData};
+use crate::{
+    algorithm::UrCalculator,
+    error::UrError,
+    frames::{Buttons, ReplayFrame, ReplayFrames},
+    options::UrOptions,
+    report::{HitError, HitErrorReport},
+};
+
+/// The baseline algorithm written with iterators and binary searches.
+pub struct ItersCalculator;
+
+impl UrCalculator for ItersCalculator {
+    fn try_calculate_report_with(
+        &self,
+        map: &Beatmap,
+        replay: &Replay,
+        options: &UrOptions,
+    ) -> Result<HitErrorReport, UrError> {
+        try_calculate_report(map, replay)
+            .map(|report| report.with_time_scale(options.clock_rate(replay), options.time_scale))
+    }
+}
 
-#[cfg_attr(not(feature = "iters"), allow(unused))]
 pub fn calculate_ur(map: &Beatmap, replay: &Replay) -> f64 {
+    ItersCalculator.calculate_ur(map, replay)
+}
+
+fn try_calculate_report(map: &Beatmap, replay: &Replay) -> Result<HitErrorReport, UrError> {
+    UrError::check_osu_mode(map, replay)?;
+
     let mods = replay
         .mods
         .without(Mod::DoubleTime)
@@ -14,32 +40,7 @@ pub fn calculate_ur(map: &Beatmap, replay: &Replay) -> f64 {
         .without(Mod::Nightcore)
         .bits();
 
-    // https://github.com/ppy/osu/blob/bbeb62ea47f9a205a10964ac332cbe157d844a78/osu.Game/Scoring/Legacy/LegacyScoreDecoder.cs#L263-L307
-    let replay_data: Vec<_> = replay
-        .replay_data
-        .as_ref()
-        .unwrap()
-        .iter()
-        .enumerate()
-        .filter(|(_, action)| action.delta != -12345)
-        .scan(0, |time_elapsed, (i, action)| {
-            *time_elapsed += action.delta;
-
-            let skip = i < 2
-                && (action.x - 256.0).abs() <= f32::EPSILON
-                && (action.y - 500.0).abs() <= f32::EPSILON;
-
-            let frame = (!skip && action.delta >= 0).then_some(ReplayData {
-                timestamp: *time_elapsed as f64,
-                x: action.x,
-                y: action.y,
-                keys: Buttons::from_f32(action.z),
-            });
-
-            Some(frame)
-        })
-        .flatten()
-        .collect();
+    let replay_data = ReplayFrames::from_replay(replay)?;
 
     let attrs = map.attributes().mods(mods).build();
 
@@ -59,25 +60,27 @@ pub fn calculate_ur(map: &Beatmap, replay: &Replay) -> f64 {
         .chain(hit_objects.iter().map(Some))
         // zip each object with its predecessor
         .zip(hit_objects.iter())
+        // keep track of the object's index
+        .enumerate()
         // filter out spinners
-        .filter(|(_, h)| !h.is_spinner())
+        .filter(|(_, (_, h))| !h.is_spinner())
         // for each object, try to find its hit frame
-        .scan(false, |prev_hit, (prev, obj)| {
+        .scan(false, |prev_hit, (i, (prev, obj))| {
             let latest_hit = match obj.is_slider() {
                 false => obj.start_time + hit_window_50,
                 true => (obj.start_time + hit_window_50).min(obj.end_time().round()),
             };
 
             let start_idx = replay_data
-                .partition_point(|frame| frame.timestamp < obj.start_time - hit_window_50);
-            let end_idx =
-                replay_data[start_idx..].partition_point(|frame| frame.timestamp <= latest_hit);
+                .partition_point(|frame| f64::from(frame.time) < obj.start_time - hit_window_50);
+            let end_idx = replay_data[start_idx..]
+                .partition_point(|frame| f64::from(frame.time) <= latest_hit);
             let frames = &replay_data[..start_idx + end_idx];
 
             // start with no keys
             let hit_error = iter::once(Buttons::default())
                 // followed by frame keys
-                .chain(frames.iter().map(|frame| frame.keys))
+                .chain(frames.iter().map(|frame| frame.buttons.distinct()))
                 // zip keys with successing frame
                 .zip(frames)
                 // skip frames that are before the object's hit window
@@ -85,27 +88,34 @@ pub fn calculate_ur(map: &Beatmap, replay: &Replay) -> f64 {
                 // filter out frames that are not hits
                 .filter_map(|(prev_frame_keys, frame)| {
                     let in_circle = is_in_circle(frame, obj, radius_sq);
-                    let press = frame.keys.is_new_press(prev_frame_keys);
+                    let press = is_new_press(frame.buttons.distinct(), prev_frame_keys);
 
-                    let notelock = prev.map_or(false, |prev| {
+                    let notelock = prev.is_some_and(|prev| {
                         let mut notelock =
-                            !*prev_hit && frame.timestamp < prev.start_time + hit_window_50;
+                            !*prev_hit && f64::from(frame.time) < prev.start_time + hit_window_50;
 
                         if prev.is_slider() {
                             let in_prev_circle = is_in_circle(frame, prev, radius_sq);
                             let sliderlock =
-                                press && in_prev_circle && frame.timestamp < prev.end_time();
+                                press && in_prev_circle && f64::from(frame.time) < prev.end_time();
                             notelock |= sliderlock;
                         }
 
                         notelock
                     });
 
-                    (in_circle && press && !notelock).then_some(frame)
+                    let keys = frame.buttons.distinct() & !prev_frame_keys;
+
+                    (in_circle && press && !notelock).then_some((frame, keys))
                 })
                 // take the first frame who's timestamp wasn't used for a previous object
-                .find(|frame| used_frames.insert(frame.timestamp.to_bits()))
-                .map(|frame| frame.timestamp - obj.start_time);
+                .find(|(frame, _)| used_frames.insert(f64::from(frame.time).to_bits()))
+                .map(|(frame, keys)| HitError {
+                    index: i,
+                    error: f64::from(frame.time) - obj.start_time,
+                    key: keys.keys().next(),
+                    pos: Some(frame.pos),
+                });
 
             *prev_hit = hit_error.is_some();
 
@@ -114,23 +124,17 @@ pub fn calculate_ur(map: &Beatmap, replay: &Replay) -> f64 {
         .flatten()
         .collect();
 
-    let sum: f64 = hit_errors.iter().sum();
-    let len = hit_errors.len() as f64;
-    let avg = sum / len;
-
-    let mut variance: f64 = 0.0;
-
-    for hit in hit_errors {
-        variance += (hit - avg) * (hit - avg);
-    }
-
-    variance /= len;
+    let n_spinners = hit_objects.iter().filter(|h| h.is_spinner()).count();
 
-    variance.sqrt() * 10.0
+    HitErrorReport::new(hit_errors, hit_objects.len(), n_spinners)
 }
 
-fn is_in_circle(frame: &ReplayData, obj: &OsuObject, radius_sq: f32) -> bool {
-    (frame.x - obj.stacked_pos().x) * (frame.x - obj.stacked_pos().x)
-        + (frame.y - obj.stacked_pos().y) * (frame.y - obj.stacked_pos().y)
+fn is_in_circle(frame: &ReplayFrame, obj: &OsuObject, radius_sq: f32) -> bool {
+    (frame.pos.x - obj.stacked_pos().x) * (frame.pos.x - obj.stacked_pos().x)
+        + (frame.pos.y - obj.stacked_pos().y) * (frame.pos.y - obj.stacked_pos().y)
         < radius_sq
 }
+
+fn is_new_press(keys: Buttons, prev: Buttons) -> bool {
+    (keys & !prev).intersects(Buttons::M1 | Buttons::M2 | Buttons::K1 | Buttons::K2)
+}
@@ -0,0 +1,203 @@
+use osu_db::{Mod, Replay};
+use rosu_pp::{parse::Pos2, Beatmap, BeatmapExt};
+
+use crate::{
+    algorithm::UrCalculator,
+    difficulty::difficulty_range,
+    error::UrError,
+    frames::ReplayFrames,
+    options::{TimeScale, UrOptions},
+    report::{HitError, HitErrorReport},
+};
+
+/// Port of osu!lazer's hit detection.
+///
+/// Lazer reports hit errors in real time, the default [`TimeScale`], so keep it to match its
+/// results screen, or use [`LazerCalculator::displayed_unstable_rate`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct LazerCalculator {
+    pub hit_policy: HitPolicy,
+}
+
+/// How lazer decides whether an object can be hit while earlier objects are still pending.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum HitPolicy {
+    /// An object can be hit once all earlier objects were judged or have started; hitting it
+    /// misses all earlier objects that are still pending.
+    #[default]
+    StartTimeOrdered,
+    /// An object can only be hit once all earlier objects were judged.
+    ObjectOrdered,
+}
+
+impl LazerCalculator {
+    /// The unstable rate as lazer's results screen shows it.
+    ///
+    /// Lazer computes it from real-time hit errors, whatever the report's [`TimeScale`], and
+    /// displays it rounded to two decimals with halves rounded away from zero.
+    pub fn displayed_unstable_rate(report: &HitErrorReport) -> f64 {
+        let unstable_rate = match report.time_scale {
+            TimeScale::MapTime => report.unstable_rate / report.clock_rate,
+            TimeScale::RealTime => report.unstable_rate,
+        };
+
+        (unstable_rate * 100.0).round() / 100.0
+    }
+}
+
+impl UrCalculator for LazerCalculator {
+    fn try_calculate_report_with(
+        &self,
+        map: &Beatmap,
+        replay: &Replay,
+        options: &UrOptions,
+    ) -> Result<HitErrorReport, UrError> {
+        try_calculate_report(map, replay, self.hit_policy)
+            .map(|report| report.with_time_scale(options.clock_rate(replay), options.time_scale))
+    }
+}
+
+pub fn calculate_ur(map: &Beatmap, replay: &Replay) -> f64 {
+    LazerCalculator::default().calculate_ur(map, replay)
+}
+
+fn try_calculate_report(
+    map: &Beatmap,
+    replay: &Replay,
+    hit_policy: HitPolicy,
+) -> Result<HitErrorReport, UrError> {
+    UrError::check_osu_mode(map, replay)?;
+
+    let mods = replay
+        .mods
+        .without(Mod::DoubleTime)
+        .without(Mod::HalfTime)
+        .without(Mod::Nightcore);
+
+    let (mut od, mut cs) = (f64::from(map.od), f64::from(map.cs));
+
+    if mods.contains(Mod::HardRock) {
+        od = (od * 1.4).min(10.0);
+        cs = (cs * 1.3).min(10.0);
+    } else if mods.contains(Mod::Easy) {
+        od /= 2.0;
+        cs /= 2.0;
+    }
+
+    /*
+        new DifficultyRange(HitResult.Great, 80, 50, 20),
+        new DifficultyRange(HitResult.Ok, 140, 100, 60),
+        new DifficultyRange(HitResult.Meh, 200, 150, 100),
+        new DifficultyRange(HitResult.Miss, 400, 400, 400),
+    */
+
+    let hit_window_50 = difficulty_range(od, 200.0, 150.0, 100.0);
+
+    let radius = OBJECT_RADIUS * (1.0 - 0.7 * (cs - 5.0) / 5.0) / 2.0;
+    let radius_sq = (radius * radius) as f32;
+
+    let frames = ReplayFrames::from_replay(replay)?.hit_frames();
+    let hit_objects = map.osu_hitobjects(mods.bits());
+    let mut hit_errors = Vec::with_capacity(hit_objects.len());
+
+    // objects that can be clicked i.e. circles and slider heads
+    let mut objects: Vec<_> = hit_objects
+        .iter()
+        .enumerate()
+        .filter(|(_, h)| !h.is_spinner())
+        .map(|(index, h)| Clickable {
+            index,
+            start_time: h.start_time,
+            pos: h.stacked_pos(),
+            judged: false,
+        })
+        .collect();
+
+    let mut first_pending = 0;
+
+    for frame in frames.iter() {
+        let time = f64::from(frame.time);
+
+        // objects whose 50 window passed without a hit are missed
+        for h in objects[first_pending..].iter_mut() {
+            if h.start_time >= time - hit_window_50 {
+                break;
+            }
+
+            h.judged = true;
+        }
+
+        while objects.get(first_pending).is_some_and(|h| h.judged) {
+            first_pending += 1;
+        }
+
+        let Some(i) = objects[first_pending..]
+            .iter()
+            .position(|h| !h.judged && frame.dist_sq(h.pos) <= radius_sq)
+            .map(|i| first_pending + i)
+        else {
+            continue;
+        };
+
+        let offset = time - objects[i].start_time;
+
+        if offset.abs() > HIT_WINDOW_MISS {
+            continue;
+        }
+
+        let start_time = objects[i].start_time;
+
+        let blocked = match hit_policy {
+            /*
+                // 1. The last blocking hitobject has been judged.
+                // 2. The current time is after the last hitobject's start time.
+                return blockingObject.Judged || time >= blockingObject.HitObject.StartTime;
+            */
+            HitPolicy::StartTimeOrdered => objects[..i]
+                .iter()
+                .rev()
+                .find(|h| h.start_time < start_time)
+                .is_some_and(|h| !h.judged && time < h.start_time),
+            HitPolicy::ObjectOrdered => objects[first_pending..i].iter().any(|h| !h.judged),
+        };
+
+        if blocked {
+            continue;
+        }
+
+        if hit_policy == HitPolicy::StartTimeOrdered {
+            // hitting an object misses all earlier ones that are still pending
+            for h in objects[first_pending..i].iter_mut() {
+                if h.start_time < start_time {
+                    h.judged = true;
+                }
+            }
+        }
+
+        // clicking within the miss window but before the 50 window is a miss
+        if offset.abs() <= hit_window_50 {
+            hit_errors.push(HitError {
+                index: objects[i].index,
+                error: offset,
+                key: Some(frame.key),
+                pos: Some(frame.pos),
+            });
+        }
+
+        objects[i].judged = true;
+    }
+
+    let n_spinners = hit_objects.iter().filter(|h| h.is_spinner()).count();
+
+    HitErrorReport::new(hit_errors, hit_objects.len(), n_spinners)
+}
+
+const OBJECT_RADIUS: f64 = 64.0;
+const HIT_WINDOW_MISS: f64 = 400.0;
+
+struct Clickable {
+    index: usize,
+    start_time: f64,
+    pos: Pos2,
+    judged: bool,
+}
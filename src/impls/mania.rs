@@ -0,0 +1,365 @@
+use osu_db::{Mod, Replay};
+use rosu_pp::{mania::ManiaObject, Beatmap, BeatmapExt, GameMode};
+
+use crate::{
+    algorithm::UrCalculator,
+    error::UrError,
+    frames::ReplayFrames,
+    judgement::{HitResult, Judgement},
+    options::UrOptions,
+    report::{ColumnReport, HitError, HitErrorReport},
+};
+
+/// Hit detection for osu!mania, including converted maps.
+///
+/// Hold notes are judged like osu!stable's ScoreV1 which combines head and tail into a single
+/// judgement, or separately for head and tail if the replay was played with ScoreV2.
+pub struct ManiaCalculator;
+
+impl UrCalculator for ManiaCalculator {
+    fn try_calculate_report_with(
+        &self,
+        map: &Beatmap,
+        replay: &Replay,
+        options: &UrOptions,
+    ) -> Result<HitErrorReport, UrError> {
+        try_calculate_report(map, replay)
+            .map(|report| report.with_time_scale(options.clock_rate(replay), options.time_scale))
+    }
+}
+
+pub fn calculate_ur(map: &Beatmap, replay: &Replay) -> f64 {
+    ManiaCalculator.calculate_ur(map, replay)
+}
+
+fn try_calculate_report(map: &Beatmap, replay: &Replay) -> Result<HitErrorReport, UrError> {
+    UrError::check_mode(map, replay, GameMode::Mania)?;
+
+    let score_v2 = (replay.mods.bits() & SCORE_V2) > 0;
+    let windows = HitWindows::new(map, replay);
+    let release_windows = windows.release();
+
+    let edges = ColumnEdges::from_replay(replay)?;
+    let hit_objects = map.mania_hitobjects();
+    let n_columns = hit_objects.iter().map(|h| h.column + 1).max().unwrap_or(0);
+
+    let mut columns: Vec<_> = (0..n_columns)
+        .map(|column| ColumnReport {
+            column,
+            ..Default::default()
+        })
+        .collect();
+
+    let mut judgements = Vec::with_capacity(hit_objects.len());
+
+    for column in columns.iter_mut() {
+        let notes: Vec<_> = (0..hit_objects.len())
+            .filter(|&i| hit_objects[i].column == column.column)
+            .collect();
+
+        let mut next = 0;
+        let mut holding: Option<(usize, f64)> = None;
+
+        let column_edges = edges.iter().filter(|edge| edge.column == column.column);
+
+        for edge in column_edges {
+            if !edge.pressed {
+                if let Some((i, head)) = holding.take() {
+                    let tail =
+                        release_offset(edge.time - hit_objects[i].end_time, &release_windows);
+
+                    if let Some(error) = tail.error {
+                        column.release_errors.push(HitError {
+                            index: i,
+                            error,
+                            key: None,
+                            pos: None,
+                        });
+                    }
+
+                    judge_hold(i, head, tail, score_v2, &windows, &mut judgements);
+                }
+
+                continue;
+            }
+
+            // notes whose window passed without a press are missed
+            while let Some(&i) = notes.get(next) {
+                if hit_objects[i].start_time + windows.miss >= edge.time {
+                    break;
+                }
+
+                judge_miss(i, &hit_objects[i], score_v2, &mut judgements);
+                next += 1;
+            }
+
+            let Some(&i) = notes.get(next) else {
+                continue;
+            };
+
+            let h = &hit_objects[i];
+            let offset = edge.time - h.start_time;
+
+            if offset < -windows.miss {
+                continue;
+            }
+
+            next += 1;
+
+            let result = windows.result(offset.abs());
+
+            if result == HitResult::Miss {
+                judge_miss(i, h, score_v2, &mut judgements);
+
+                continue;
+            }
+
+            column.hit_errors.push(HitError {
+                index: i,
+                error: offset,
+                key: None,
+                pos: None,
+            });
+
+            if is_hold(h) {
+                holding = Some((i, offset.abs()));
+            } else {
+                judgements.push(Judgement { index: i, result });
+            }
+        }
+
+        // hold notes that were never released were held until the end of the replay
+        if let Some((i, head)) = holding {
+            let tail = Release {
+                offset: 0.0,
+                error: None,
+            };
+
+            judge_hold(i, head, tail, score_v2, &windows, &mut judgements);
+        }
+
+        for &i in notes[next..].iter() {
+            judge_miss(i, &hit_objects[i], score_v2, &mut judgements);
+        }
+    }
+
+    judgements.sort_by_key(|judgement| judgement.index);
+
+    let mut hit_errors: Vec<_> = columns
+        .iter()
+        .flat_map(|column| column.hit_errors.iter().copied())
+        .collect();
+
+    hit_errors.sort_by_key(|hit_error| hit_error.index);
+
+    HitErrorReport::new(hit_errors, hit_objects.len(), 0)
+        .map(|report| report.with_judgements(judgements).with_columns(columns))
+}
+
+const SCORE_V2: u32 = 1 << 29;
+
+fn is_hold(h: &ManiaObject) -> bool {
+    h.end_time > h.start_time
+}
+
+fn judge_miss(index: usize, h: &ManiaObject, score_v2: bool, judgements: &mut Vec<Judgement>) {
+    let result = HitResult::Miss;
+    judgements.push(Judgement { index, result });
+
+    if score_v2 && is_hold(h) {
+        judgements.push(Judgement { index, result });
+    }
+}
+
+fn judge_hold(
+    index: usize,
+    head: f64,
+    tail: Release,
+    score_v2: bool,
+    windows: &HitWindows,
+    judgements: &mut Vec<Judgement>,
+) {
+    if score_v2 {
+        let release_windows = windows.release();
+
+        judgements.push(Judgement {
+            index,
+            result: windows.result(head),
+        });
+
+        judgements.push(Judgement {
+            index,
+            result: release_windows.result(tail.offset.abs()),
+        });
+
+        return;
+    }
+
+    /*
+        if (headOffset <= MAX * 1.2 && headOffset + tailOffset <= MAX * 2.4) return Max;
+        if (headOffset <= 300 * 1.1 && headOffset + tailOffset <= 300 * 2.2) return 300;
+        if (headOffset <= 200 && headOffset + tailOffset <= 200 * 2) return 200;
+        if (headOffset <= 100 && headOffset + tailOffset <= 100 * 2) return 100;
+        return 50;
+    */
+
+    let sum = head + tail.offset.abs();
+
+    let result = if tail.offset.is_nan() {
+        HitResult::Hit50
+    } else if head <= windows.max * 1.2 && sum <= windows.max * 2.4 {
+        HitResult::HitMax
+    } else if head <= windows.w300 * 1.1 && sum <= windows.w300 * 2.2 {
+        HitResult::Hit300
+    } else if head <= windows.w200 && sum <= windows.w200 * 2.0 {
+        HitResult::Hit200
+    } else if head <= windows.w100 && sum <= windows.w100 * 2.0 {
+        HitResult::Hit100
+    } else {
+        HitResult::Hit50
+    };
+
+    judgements.push(Judgement { index, result });
+}
+
+/// How a hold note's tail was released.
+struct Release {
+    /// Offset to the tail that is judged; NaN if the hold note was released too early.
+    offset: f64,
+    /// Offset to the tail if it was released within the miss window.
+    error: Option<f64>,
+}
+
+fn release_offset(offset: f64, release_windows: &HitWindows) -> Release {
+    let error = (offset.abs() <= release_windows.miss).then_some(offset);
+
+    let offset = if offset < -release_windows.w50 {
+        f64::NAN
+    } else {
+        // releasing late beyond the window counts as the latest possible release
+        offset.min(release_windows.w50)
+    };
+
+    Release { offset, error }
+}
+
+/// Hit windows in milliseconds to either side of a note.
+struct HitWindows {
+    max: f64,
+    w300: f64,
+    w200: f64,
+    w100: f64,
+    w50: f64,
+    miss: f64,
+}
+
+impl HitWindows {
+    /// Hold note tails may be released later or earlier than notes can be hit.
+    const RELEASE_LENIENCE: f64 = 1.5;
+
+    fn new(map: &Beatmap, replay: &Replay) -> Self {
+        let od = f64::from(map.od);
+
+        // converted maps use fixed windows
+        let windows = if map.mode == GameMode::Osu {
+            let w300 = if od > 4.0 { 34.0 } else { 47.0 };
+            let w200 = if od > 4.0 { 67.0 } else { 77.0 };
+
+            [16.0, w300, w200, 97.0, 121.0, 158.0]
+        } else {
+            [
+                16.0,
+                64.0 - 3.0 * od,
+                97.0 - 3.0 * od,
+                127.0 - 3.0 * od,
+                151.0 - 3.0 * od,
+                188.0 - 3.0 * od,
+            ]
+        };
+
+        let factor = if replay.mods.contains(Mod::HardRock) {
+            1.0 / 1.4
+        } else if replay.mods.contains(Mod::Easy) {
+            1.4
+        } else {
+            1.0
+        };
+
+        // osu!stable works with integer windows
+        let [max, w300, w200, w100, w50, miss] = windows.map(|w| (w * factor).floor());
+
+        Self {
+            max,
+            w300,
+            w200,
+            w100,
+            w50,
+            miss,
+        }
+    }
+
+    fn release(&self) -> Self {
+        Self {
+            max: self.max * Self::RELEASE_LENIENCE,
+            w300: self.w300 * Self::RELEASE_LENIENCE,
+            w200: self.w200 * Self::RELEASE_LENIENCE,
+            w100: self.w100 * Self::RELEASE_LENIENCE,
+            w50: self.w50 * Self::RELEASE_LENIENCE,
+            miss: self.miss * Self::RELEASE_LENIENCE,
+        }
+    }
+
+    fn result(&self, offset: f64) -> HitResult {
+        if offset <= self.max {
+            HitResult::HitMax
+        } else if offset <= self.w300 {
+            HitResult::Hit300
+        } else if offset <= self.w200 {
+            HitResult::Hit200
+        } else if offset <= self.w100 {
+            HitResult::Hit100
+        } else if offset <= self.w50 {
+            HitResult::Hit50
+        } else {
+            HitResult::Miss
+        }
+    }
+}
+
+/// A column being pressed or released.
+struct ColumnEdge {
+    time: f64,
+    column: usize,
+    pressed: bool,
+}
+
+struct ColumnEdges;
+
+impl ColumnEdges {
+    fn from_replay(replay: &Replay) -> Result<Vec<ColumnEdge>, UrError> {
+        let mut prev_columns = 0;
+
+        let edges = ReplayFrames::from_replay(replay)?
+            .iter()
+            .flat_map(|frame| {
+                // each bit of x is the pressed state of a column
+                let columns = frame.pos.x as u32;
+
+                let changed = columns ^ prev_columns;
+                prev_columns = columns;
+
+                let time = f64::from(frame.time);
+
+                (0..u32::BITS as usize)
+                    .filter(move |column| (changed & (1 << column)) > 0)
+                    .map(move |column| ColumnEdge {
+                        time,
+                        column,
+                        pressed: (columns & (1 << column)) > 0,
+                    })
+            })
+            .collect();
+
+        Ok(edges)
+    }
+}
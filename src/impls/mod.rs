@@ -1,6 +1,10 @@
 pub mod baseline;
+pub mod catch;
 pub mod circleguard;
 pub mod custom1;
 pub mod custom2;
 pub mod iters;
+pub mod lazer;
+pub mod mania;
 pub mod stable;
+pub mod taiko;
@@ -0,0 +1,413 @@
+use std::{cmp::Ordering, convert::identity, f64::consts::PI, iter};
+
+use rosu_pp::{
+    osu::OsuObject,
+    parse::{HitObject, HitObjectKind, PathControlPoint, PathType, Pos2},
+};
+
+const BEZIER_TOLERANCE: f32 = 0.25;
+const CATMULL_DETAIL: usize = 50;
+const CIRCULAR_ARC_TOLERANCE: f32 = 0.1;
+
+/// The path a slider's ball travels along.
+///
+/// Port of rosu-pp's `Curve`, which places ticks and repeats but is not public, so the ball
+/// follows the same path as the slider's nested objects. Positions are relative to the head.
+pub struct SliderPath {
+    path: Vec<Pos2>,
+    lengths: Vec<f64>,
+    span_count: usize,
+}
+
+impl SliderPath {
+    /// The path of the slider, flipped vertically for HardRock. `None` if the object is no
+    /// slider.
+    pub fn new(h: &HitObject, hard_rock: bool) -> Option<Self> {
+        let HitObjectKind::Slider {
+            pixel_len,
+            repeats,
+            ref control_points,
+            ..
+        } = h.kind
+        else {
+            return None;
+        };
+
+        let points: Vec<_> = control_points
+            .iter()
+            .map(|point| PathControlPoint {
+                pos: if hard_rock {
+                    Pos2 {
+                        x: point.pos.x,
+                        y: -point.pos.y,
+                    }
+                } else {
+                    point.pos
+                },
+                kind: point.kind,
+            })
+            .collect();
+
+        let mut path = calculate_path(&points);
+        let lengths = calculate_lengths(&points, &mut path, pixel_len);
+
+        Some(Self {
+            path,
+            lengths,
+            span_count: repeats + 1,
+        })
+    }
+
+    /// Position of the ball of the slider `h` that ends at `end_time`, including its stack
+    /// offset.
+    pub fn ball_pos(&self, h: &OsuObject, end_time: f64, time: f64) -> Pos2 {
+        let duration = end_time - h.start_time;
+
+        let progress = if duration > 0.0 {
+            ((time - h.start_time) / duration).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+
+        // the ball moves back and forth once per span
+        let spans = progress * self.span_count as f64;
+        let mut span_progress = spans % 1.0;
+
+        if spans as usize % 2 == 1 {
+            span_progress = 1.0 - span_progress;
+        }
+
+        h.pos + self.position_at(span_progress) + h.stack_offset
+    }
+
+    fn position_at(&self, progress: f64) -> Pos2 {
+        let d = progress.clamp(0.0, 1.0) * self.dist();
+
+        let i = self
+            .lengths
+            .binary_search_by(|len| len.partial_cmp(&d).unwrap_or(Ordering::Equal))
+            .map_or_else(identity, identity);
+
+        self.interpolate_vertices(i, d)
+    }
+
+    fn dist(&self) -> f64 {
+        self.lengths.last().copied().unwrap_or(0.0)
+    }
+
+    fn interpolate_vertices(&self, i: usize, d: f64) -> Pos2 {
+        if self.path.is_empty() {
+            return Pos2::zero();
+        }
+
+        let p1 = if i == 0 {
+            return self.path[0];
+        } else if let Some(p) = self.path.get(i) {
+            *p
+        } else {
+            return self.path[self.path.len() - 1];
+        };
+
+        let p0 = self.path[i - 1];
+
+        let d0 = self.lengths[i - 1];
+        let d1 = self.lengths[i];
+
+        // avoid dividing by almost zero if two points are extremely close to each other
+        if (d0 - d1).abs() <= f64::EPSILON {
+            return p0;
+        }
+
+        let w = (d - d0) / (d1 - d0);
+
+        p0 + (p1 - p0) * w as f32
+    }
+}
+
+fn calculate_path(points: &[PathControlPoint]) -> Vec<Pos2> {
+    let mut path = Vec::new();
+
+    let vertices: Vec<_> = points.iter().map(|point| point.pos).collect();
+    let mut start = 0;
+
+    for (i, point) in points.iter().enumerate() {
+        if point.kind.is_none() && i < points.len() - 1 {
+            continue;
+        }
+
+        // the current vertex ends the segment
+        let segment = &vertices[start..=i];
+
+        match points[start].kind.unwrap_or(PathType::Linear) {
+            PathType::Bezier => approximate_bezier(&mut path, segment),
+            PathType::Catmull => approximate_catmull(&mut path, segment),
+            PathType::Linear => path.extend(segment),
+            PathType::PerfectCurve => match *segment {
+                [a, b, c] if approximate_circular_arc(&mut path, a, b, c) => {}
+                _ => approximate_bezier(&mut path, segment),
+            },
+        }
+
+        // the new segment starts at the current vertex
+        start = i;
+    }
+
+    path.dedup();
+
+    path
+}
+
+/// Cumulative lengths along the path, which is shortened or extended to the expected length.
+fn calculate_lengths(
+    points: &[PathControlPoint],
+    path: &mut Vec<Pos2>,
+    expected_len: Option<f64>,
+) -> Vec<f64> {
+    let mut lengths = Vec::with_capacity(path.len());
+    let mut calculated_len = 0.0;
+    lengths.push(0.0);
+
+    for pair in path.windows(2) {
+        calculated_len += f64::from((pair[1] - pair[0]).length());
+        lengths.push(calculated_len);
+    }
+
+    let Some(expected_len) = expected_len.filter(|&len| len != calculated_len) else {
+        return lengths;
+    };
+
+    // osu!stable does not extend sliders whose last two control points are equal
+    if let [.., a, b] = points {
+        if a.pos == b.pos && expected_len > calculated_len {
+            lengths.push(calculated_len);
+
+            return lengths;
+        }
+    }
+
+    if lengths.len() == 1 {
+        return lengths;
+    }
+
+    // the last length is always incorrect
+    lengths.pop();
+
+    let last_valid = lengths
+        .iter()
+        .rev()
+        .position(|&len| len < expected_len)
+        .map_or(0, |i| lengths.len() - i);
+
+    // trim the lengths and path segments that lie beyond the expected length
+    if last_valid < lengths.len() {
+        lengths.truncate(last_valid);
+        path.truncate(last_valid + 1);
+
+        if lengths.is_empty() {
+            lengths.push(0.0);
+
+            return lengths;
+        }
+    }
+
+    let end = lengths.len();
+    let prev = end - 1;
+
+    // shorten or lengthen the last segment
+    let dir = (path[end] - path[prev]).normalize();
+
+    path[end] = path[prev] + dir * (expected_len - lengths[prev]) as f32;
+    lengths.push(expected_len);
+
+    lengths
+}
+
+fn approximate_bezier(path: &mut Vec<Pos2>, points: &[Pos2]) {
+    // curves that are not yet flat enough, a stack instead of recursion
+    let mut to_flatten = vec![points.to_vec()];
+
+    while let Some(parent) = to_flatten.pop() {
+        if bezier_is_flat_enough(&parent) {
+            bezier_approximate(path, &parent);
+
+            continue;
+        }
+
+        let (left, right) = bezier_subdivide(&parent);
+
+        to_flatten.push(right);
+        to_flatten.push(left);
+    }
+
+    path.push(points[points.len() - 1]);
+}
+
+fn bezier_is_flat_enough(points: &[Pos2]) -> bool {
+    let limit = BEZIER_TOLERANCE * BEZIER_TOLERANCE * 4.0;
+
+    !points
+        .windows(3)
+        .any(|w| (w[0] - w[1] * 2.0 + w[2]).length_squared() > limit)
+}
+
+/// Split the curve into two halves using De Casteljau's algorithm.
+fn bezier_subdivide(points: &[Pos2]) -> (Vec<Pos2>, Vec<Pos2>) {
+    let count = points.len();
+    let mut midpoints = points.to_vec();
+    let mut left = vec![Pos2::zero(); count];
+    let mut right = vec![Pos2::zero(); count];
+
+    for i in (1..count).rev() {
+        left[count - i - 1] = midpoints[0];
+        right[i] = midpoints[i];
+
+        for j in 0..i {
+            midpoints[j] = (midpoints[j] + midpoints[j + 1]) / 2.0;
+        }
+    }
+
+    left[count - 1] = midpoints[0];
+    right[0] = midpoints[0];
+
+    (left, right)
+}
+
+/// Piecewise-linear approximation of a flat curve with as many points as control points.
+fn bezier_approximate(path: &mut Vec<Pos2>, points: &[Pos2]) {
+    let (left, right) = bezier_subdivide(points);
+    path.push(points[0]);
+
+    let halves: Vec<_> = left.iter().chain(&right[1..]).copied().collect();
+
+    for i in 1..points.len().saturating_sub(1) {
+        let j = 2 * i;
+        path.push((halves[j - 1] + halves[j] * 2.0 + halves[j + 1]) * 0.25);
+    }
+}
+
+fn approximate_catmull(path: &mut Vec<Pos2>, points: &[Pos2]) {
+    if points.len() == 1 {
+        return;
+    }
+
+    // the first iteration has no vertex before the first point
+    let v1 = points[0];
+    let v2 = points[0];
+    let v3 = points.get(1).copied().unwrap_or(v2);
+    let v4 = points.get(2).copied().unwrap_or_else(|| v3 * 2.0 - v2);
+
+    catmull_subpath(path, v1, v2, v3, v4);
+
+    for (i, pair) in (2..points.len()).zip(points.windows(2)) {
+        let (v1, v2) = (pair[0], pair[1]);
+        let v3 = points.get(i).copied().unwrap_or_else(|| v2 * 2.0 - v1);
+        let v4 = points.get(i + 1).copied().unwrap_or_else(|| v3 * 2.0 - v2);
+
+        catmull_subpath(path, v1, v2, v3, v4);
+    }
+}
+
+fn catmull_subpath(path: &mut Vec<Pos2>, v1: Pos2, v2: Pos2, v3: Pos2, v4: Pos2) {
+    let x1 = 2.0 * v2.x;
+    let x2 = -v1.x + v3.x;
+    let x3 = 2.0 * v1.x - 5.0 * v2.x + 4.0 * v3.x - v4.x;
+    let x4 = -v1.x + 3.0 * (v2.x - v3.x) + v4.x;
+
+    let y1 = 2.0 * v2.y;
+    let y2 = -v1.y + v3.y;
+    let y3 = 2.0 * v1.y - 5.0 * v2.y + 4.0 * v3.y - v4.y;
+    let y4 = -v1.y + 3.0 * (v2.y - v3.y) + v4.y;
+
+    let point_at = |t1: f32| {
+        let t2 = t1 * t1;
+        let t3 = t2 * t1;
+
+        Pos2 {
+            x: 0.5 * (x1 + x2 * t1 + x3 * t2 + x4 * t3),
+            y: 0.5 * (y1 + y2 * t1 + y3 * t2 + y4 * t3),
+        }
+    };
+
+    let detail = CATMULL_DETAIL as f32;
+
+    path.extend((0..CATMULL_DETAIL).flat_map(|c| {
+        let c = c as f32;
+
+        iter::once(point_at(c / detail)).chain(iter::once(point_at((c + 1.0) / detail)))
+    }));
+}
+
+/// Approximate the arc through the three points, `false` if they lie on a line.
+fn approximate_circular_arc(path: &mut Vec<Pos2>, a: Pos2, b: Pos2, c: Pos2) -> bool {
+    // degenerate triangles fall back to a bezier curve
+    if ((b.y - a.y) * (c.x - a.x) - (b.x - a.x) * (c.y - a.y)).abs() <= f32::EPSILON {
+        return false;
+    }
+
+    let d = 2.0 * (a.x * (b - c).y + b.x * (c - a).y + c.x * (a - b).y);
+    let a_sq = a.length_squared();
+    let b_sq = b.length_squared();
+    let c_sq = c.length_squared();
+
+    let centre = Pos2 {
+        x: (a_sq * (b - c).y + b_sq * (c - a).y + c_sq * (a - b).y) / d,
+        y: (a_sq * (c - b).x + b_sq * (a - c).x + c_sq * (b - a).x) / d,
+    };
+
+    let d_a = a - centre;
+    let d_c = c - centre;
+
+    let radius = d_a.length();
+
+    let theta_start = f64::from(d_a.y).atan2(f64::from(d_a.x));
+    let mut theta_end = f64::from(d_c.y).atan2(f64::from(d_c.x));
+
+    while theta_end < theta_start {
+        theta_end += 2.0 * PI;
+    }
+
+    let mut direction = 1.0;
+    let mut theta_range = theta_end - theta_start;
+
+    // draw the arc on the side of AC that B lies on
+    let a_to_c = c - a;
+
+    let ortho_a_to_c = Pos2 {
+        x: a_to_c.y,
+        y: -a_to_c.x,
+    };
+
+    if ortho_a_to_c.dot(b - a) < 0.0 {
+        direction = -direction;
+        theta_range = 2.0 * PI - theta_range;
+    }
+
+    // enough points to keep the discrete curvature within the tolerance
+    let n_points = if 2.0 * radius <= CIRCULAR_ARC_TOLERANCE {
+        2
+    } else {
+        let divisor = 2.0 * (1.0 - CIRCULAR_ARC_TOLERANCE / radius).acos();
+
+        // C# casts an infinite amount to `i32::MIN`, see map id 2568364
+        if divisor.abs() <= f32::EPSILON {
+            2
+        } else {
+            ((theta_range / f64::from(divisor)).ceil() as usize).max(2)
+        }
+    };
+
+    let directed_range = direction * theta_range;
+
+    path.extend((0..n_points).map(|i| {
+        let theta = theta_start + i as f64 / (n_points - 1) as f64 * directed_range;
+        let (sin, cos) = theta.sin_cos();
+
+        centre
+            + Pos2 {
+                x: cos as f32,
+                y: sin as f32,
+            } * radius
+    }));
+
+    true
+}
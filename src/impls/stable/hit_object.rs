@@ -5,15 +5,17 @@ use rosu_pp::{
     parse::Pos2,
 };
 
-use super::{frames::HitFrame, hit_object_manager::HitObjectManager};
+use crate::frames::HitFrame;
 
-pub struct HitObject<'h> {
+use super::hit_object_manager::HitObjectManager;
+
+pub struct HitObject {
     pub is_hit: bool,
-    h: &'h dyn HitObjectExt,
+    h: Box<dyn HitObjectExt>,
 }
 
-impl<'h> HitObject<'h> {
-    pub fn new(h: &'h dyn HitObjectExt) -> Self {
+impl HitObject {
+    pub fn new(h: Box<dyn HitObjectExt>) -> Self {
         Self { h, is_hit: false }
     }
 
@@ -22,8 +24,8 @@ impl<'h> HitObject<'h> {
     }
 }
 
-impl HitObject<'_> {
-    pub fn hit_test(&self, frame: &HitFrame, manager: &HitObjectManager<'_>) -> bool {
+impl HitObject {
+    pub fn hit_test(&self, frame: &HitFrame, manager: &HitObjectManager) -> bool {
         /*
             return ((!hittableRangeOnly && IsVisible) ||
                   (StartTime - hitObjectManager.PreEmpt <= AudioEngine.Time &&
@@ -42,7 +44,7 @@ impl HitObject<'_> {
         let matches_time = self.start_time() - manager.preempt <= frame.time
             && self.start_time() + manager.hit_window_50 >= frame.time;
 
-        let matches_pos = frame.pos.dist_sq(self.pos()) <= manager.radius_sq;
+        let matches_pos = frame.dist_sq(self.pos()) <= manager.radius_sq;
 
         matches_time && matches_pos && !self.is_hit
     }
@@ -51,6 +53,20 @@ impl HitObject<'_> {
         self.h.start_time()
     }
 
+    /// Time until which the object locks clicks on later objects if it's not hit.
+    ///
+    /// Before the sliderbug fix, a slider stopped locking once it ended even if that was
+    /// before its 50 hit window ended.
+    pub fn notelock_end_time(&self, manager: &HitObjectManager) -> i32 {
+        let end_time = self.start_time() + manager.hit_window_50;
+
+        if self.is_slider() && !manager.sliderbug_fixed {
+            end_time.min(self.h.end_time())
+        } else {
+            end_time
+        }
+    }
+
     fn pos(&self) -> Pos2 {
         self.h.pos()
     }
@@ -67,18 +83,19 @@ impl HitObject<'_> {
         self.h.is_slider()
     }
 
-    pub fn is_visible(&self, time: i32, manager: &HitObjectManager<'_>) -> bool {
+    pub fn is_visible(&self, time: i32, manager: &HitObjectManager) -> bool {
         self.h.is_visible(time, manager)
     }
 }
 
 pub trait HitObjectExt {
     fn start_time(&self) -> i32;
+    fn end_time(&self) -> i32;
     fn pos(&self) -> Pos2;
     fn stack_count(&self) -> i32;
     fn is_normal(&self) -> bool;
     fn is_slider(&self) -> bool;
-    fn is_visible(&self, time: i32, manager: &HitObjectManager<'_>) -> bool;
+    fn is_visible(&self, time: i32, manager: &HitObjectManager) -> bool;
 }
 
 impl HitObjectExt for OsuObject {
@@ -87,6 +104,11 @@ impl HitObjectExt for OsuObject {
         self.start_time as i32
     }
 
+    #[inline]
+    fn end_time(&self) -> i32 {
+        OsuObject::end_time(self) as i32
+    }
+
     #[inline]
     fn pos(&self) -> Pos2 {
         self.stacked_pos()
@@ -108,7 +130,7 @@ impl HitObjectExt for OsuObject {
     }
 
     #[inline]
-    fn is_visible(&self, time: i32, manager: &HitObjectManager<'_>) -> bool {
+    fn is_visible(&self, time: i32, manager: &HitObjectManager) -> bool {
         match self.kind {
             OsuObjectKind::Circle => {
                 time >= self.start_time as i32 - manager.preempt
@@ -126,7 +148,7 @@ impl HitObjectExt for OsuObject {
     }
 }
 
-impl Debug for HitObject<'_> {
+impl Debug for HitObject {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         #[derive(Debug)]
         enum HitObjectKind {
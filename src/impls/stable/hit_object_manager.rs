@@ -1,44 +1,56 @@
 use rosu_pp::Beatmap;
 
-use super::{
-    frames::HitFrame,
-    hit_object::{HitObject, HitObjectExt},
-};
+use crate::{difficulty::circle_radius, frames::HitFrame, judgement::HitResult};
 
-pub struct HitObjectManager<'h> {
+use super::hit_object::{HitObject, HitObjectExt};
+
+pub struct HitObjectManager {
+    pub hit_window_300: i32,
+    pub hit_window_100: i32,
     pub hit_window_50: i32,
     pub preempt: i32,
     pub radius_sq: f32,
+    pub sliderbug_fixed: bool,
     minimal_start: usize,
     minimal_end: usize,
-    hit_objects: Vec<HitObject<'h>>,
+    hit_objects: Vec<HitObject>,
 }
 
-impl HitObjectManager<'_> {
+impl HitObjectManager {
     pub const HITTABLE_RANGE: i32 = 400;
     pub const FADE_IN: i32 = 400;
     pub const FADE_OUT: i32 = 240;
 }
 
-impl<'h> HitObjectManager<'h> {
-    pub fn new<O: HitObjectExt>(hit_objects: &'h [O], map: &Beatmap, mods: u32) -> Self {
+impl HitObjectManager {
+    pub fn new<O: HitObjectExt + Clone + 'static>(
+        hit_objects: &[O],
+        map: &Beatmap,
+        mods: u32,
+        sliderbug_fixed: bool,
+    ) -> Self {
         let attrs = map.attributes().mods(mods).build();
 
-        let adjusted_cs = ((attrs.cs - 5.0) / 5.0) as f32;
-        let sprite_display_size = 512.0 / 8.0 * (1.0 - 0.7 * adjusted_cs);
-        const BROKEN_GAMEFIELD_ROUNDING_ALLOWANCE: f32 = 1.00041;
-        let radius = sprite_display_size / 2.0 / 1.0 * BROKEN_GAMEFIELD_ROUNDING_ALLOWANCE;
+        let radius = circle_radius(attrs.cs);
         let radius_sq = radius * radius;
 
+        let hit_window_300 = map_difficulty_range(map.od, 80.0, 50.0, 20.0, mods) as i32;
+        let hit_window_100 = map_difficulty_range(map.od, 140.0, 100.0, 60.0, mods) as i32;
         let hit_window_50 = map_difficulty_range(map.od, 200.0, 150.0, 100.0, mods) as i32;
         let preempt = map_difficulty_range(map.ar, 1800.0, 1200.0, 450.0, mods) as i32;
 
-        let hit_objects = hit_objects.iter().map(|h| HitObject::new(h)).collect();
+        let hit_objects = hit_objects
+            .iter()
+            .map(|h| HitObject::new(Box::new(h.clone())))
+            .collect();
 
         Self {
+            hit_window_300,
+            hit_window_100,
             hit_window_50,
             preempt,
             radius_sq,
+            sliderbug_fixed,
             hit_objects,
             minimal_start: 0,
             minimal_end: 0,
@@ -46,7 +58,7 @@ impl<'h> HitObjectManager<'h> {
     }
 }
 
-impl<'h> HitObjectManager<'h> {
+impl HitObjectManager {
     pub fn update(&mut self, time: i32) {
         let min_left = time - self.preempt;
         let min_right = time + self.preempt;
@@ -60,11 +72,11 @@ impl<'h> HitObjectManager<'h> {
                 .partition_point(|h| h.start_time() < min_right);
     }
 
-    pub fn hit_objects_minimal(&self) -> &[HitObject<'h>] {
+    pub fn hit_objects_minimal(&self) -> &[HitObject] {
         &self.hit_objects[self.minimal_start..self.minimal_end]
     }
 
-    pub fn find_circle_at(&self, frame: &HitFrame) -> Option<(usize, &HitObject<'h>)> {
+    pub fn find_circle_at(&self, frame: &HitFrame) -> Option<(usize, &HitObject)> {
         /*
             Vector2 v = new Vector2(x, y);
 
@@ -80,6 +92,29 @@ impl<'h> HitObjectManager<'h> {
             .find(|(_, h)| h.hit_test(frame, self))
     }
 
+    pub fn hit_result(&self, accuracy: i32) -> HitResult {
+        /*
+            if (accuracy < hitObjectManager.HitWindow300) return IncreaseScoreType.Hit300;
+            if (accuracy < hitObjectManager.HitWindow100) return IncreaseScoreType.Hit100;
+            if (accuracy < hitObjectManager.HitWindow50) return IncreaseScoreType.Hit50;
+            return IncreaseScoreType.Miss;
+        */
+
+        if accuracy < self.hit_window_300 {
+            HitResult::Hit300
+        } else if accuracy < self.hit_window_100 {
+            HitResult::Hit100
+        } else if accuracy < self.hit_window_50 {
+            HitResult::Hit50
+        } else {
+            HitResult::Miss
+        }
+    }
+
+    pub fn object_index(&self, index: usize) -> usize {
+        self.minimal_start + index
+    }
+
     pub fn hit(&mut self, index: usize) {
         self.hit_objects[self.minimal_start + index].hit()
     }
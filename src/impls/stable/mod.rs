@@ -1,60 +1,58 @@
-use osu_db::{Mod, Replay};
-use rosu_pp::{Beatmap, BeatmapExt};
-
-use self::{
-    error_stats::ErrorStatistics,
-    frames::HitFrames,
-    hit_object_manager::HitObjectManager,
-    ruleset::{ClickAction, Ruleset},
+use osu_db::Replay;
+use rosu_pp::Beatmap;
+
+use crate::{
+    algorithm::UrCalculator, error::UrError, frames::ReplayFrames, options::UrOptions,
+    report::HitErrorReport,
 };
 
-mod error_stats;
-mod frames;
+pub use self::tracker::UrTracker;
+
+mod curve;
 mod hit_object;
 mod hit_object_manager;
 mod ruleset;
+mod slider;
+mod tracker;
+
+/// Port of osu!stable's `HitObjectManager` and `Ruleset` hit detection.
+pub struct StableCalculator;
+
+impl UrCalculator for StableCalculator {
+    fn try_calculate_report_with(
+        &self,
+        map: &Beatmap,
+        replay: &Replay,
+        options: &UrOptions,
+    ) -> Result<HitErrorReport, UrError> {
+        try_calculate_report(map, replay, options)
+    }
+}
 
-#[cfg_attr(not(feature = "stable"), allow(unused))]
 pub fn calculate_ur(map: &Beatmap, replay: &Replay) -> f64 {
-    let mods = replay
-        .mods
-        .without(Mod::DoubleTime)
-        .without(Mod::HalfTime)
-        .without(Mod::Nightcore)
-        .bits();
-
-    let frames = HitFrames::from_replay(replay);
-    let hit_objects = map.osu_hitobjects(mods);
-    let mut hit_errors = Vec::with_capacity(hit_objects.len());
-    let mut manager = HitObjectManager::new(&hit_objects, map, mods);
-
-    for frame in frames.iter() {
-        manager.update(frame.time);
-
-        let Some((i, h)) = manager.find_circle_at(frame) else {
-            continue;
-        };
-
-        match Ruleset::check_click_action(h, i, frame, &manager) {
-            ClickAction::Hit => {
-                if h.is_normal() {
-                    let accuracy = (frame.time - h.start_time()).abs();
-
-                    if accuracy < manager.hit_window_50 {
-                        hit_errors.push(frame.time - h.start_time());
-                    }
-
-                    manager.hit(i);
-                } else if h.is_slider() && !h.is_hit {
-                    hit_errors.push(frame.time - h.start_time());
-                    manager.hit(i);
-                }
-            }
-            ClickAction::Ignore | ClickAction::Shake => {}
-        }
-    }
+    StableCalculator.calculate_ur(map, replay)
+}
 
-    let stats = ErrorStatistics::new(&hit_errors);
+fn try_calculate_report(
+    map: &Beatmap,
+    replay: &Replay,
+    options: &UrOptions,
+) -> Result<HitErrorReport, UrError> {
+    UrError::check_osu_mode(map, replay)?;
+
+    let frames = ReplayFrames::from_replay(replay)?;
+
+    let options = UrOptions {
+        clock_rate: Some(options.clock_rate(replay)),
+        sliderbug_fixed: Some(options.sliderbug_fixed(replay)),
+        ..options.clone()
+    };
+
+    let mut tracker = UrTracker::with_options(map, replay.mods, &options)?;
+
+    for frame in frames {
+        tracker.push_frame(frame);
+    }
 
-    stats.unstable_rate
+    tracker.finish()
 }
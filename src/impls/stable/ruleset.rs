@@ -1,13 +1,15 @@
-use super::{frames::HitFrame, hit_object::HitObject, hit_object_manager::HitObjectManager};
+use crate::frames::HitFrame;
+
+use super::{hit_object::HitObject, hit_object_manager::HitObjectManager};
 
 pub struct Ruleset;
 
 impl Ruleset {
     pub fn check_click_action(
-        h: &HitObject<'_>,
+        h: &HitObject,
         index: usize,
         frame: &HitFrame,
-        manager: &HitObjectManager<'_>,
+        manager: &HitObjectManager,
     ) -> ClickAction {
         /*
             if (h.IsType(HitObjectType.Normal))
@@ -45,7 +47,7 @@ impl Ruleset {
         let mut is_next_circle = true;
 
         for (j, t) in manager.hit_objects_minimal().iter().enumerate() {
-            if t.start_time() + manager.hit_window_50 <= frame.time || t.is_hit {
+            if t.notelock_end_time(manager) <= frame.time || t.is_hit {
                 continue;
             }
 
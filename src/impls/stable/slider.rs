@@ -0,0 +1,141 @@
+use rosu_pp::{
+    osu::{NestedObjectKind, OsuObject, OsuObjectKind},
+    parse::Pos2,
+};
+
+use crate::{frames::ReplayFrame, judgement::SliderResult};
+
+use super::curve::SliderPath;
+
+/// Checks whether the cursor follows a slider's ball up to each of its nested objects.
+///
+/// Every frame updates the follow circle: it grows while the ball is tracked and shrinks back
+/// to the circle radius once all keys are released or the cursor leaves it. Ticks, repeats,
+/// and the tail are hit if the ball is tracked when they are due.
+pub struct SliderTracker<'f> {
+    frames: &'f [ReplayFrame],
+    radius_sq: f32,
+}
+
+impl<'f> SliderTracker<'f> {
+    /// Once tracking, the follow circle grows to this multiple of the circle radius.
+    const FOLLOW_RADIUS_FACTOR: f32 = 2.4;
+
+    pub fn new(frames: &'f [ReplayFrame], radius_sq: f32) -> Self {
+        Self { frames, radius_sq }
+    }
+
+    pub fn track(
+        &self,
+        index: usize,
+        h: &OsuObject,
+        path: &SliderPath,
+        head_hit: bool,
+    ) -> Option<SliderResult> {
+        let OsuObjectKind::Slider(ref slider) = h.kind else {
+            return None;
+        };
+
+        let mut nested: Vec<_> = slider.nested_objects.iter().collect();
+        nested.sort_by(|a, b| a.start_time.total_cmp(&b.start_time));
+
+        // the tail is checked before the slider ends so the ball is not necessarily at its end
+        let ball_pos = |time| path.ball_pos(h, slider.end_time, time);
+
+        let mut result = SliderResult {
+            index,
+            head_hit,
+            ..Default::default()
+        };
+
+        let mut tracking = head_hit;
+        let mut prev_time = h.start_time;
+        let mut combo = Combo::default();
+        combo.add(head_hit, true);
+
+        for nested in nested {
+            tracking = self.follow(ball_pos, prev_time, nested.start_time, tracking);
+            prev_time = nested.start_time;
+
+            match (nested.kind, tracking) {
+                (NestedObjectKind::Tick, true) => result.ticks_hit += 1,
+                (NestedObjectKind::Tick, false) => result.ticks_missed += 1,
+                (NestedObjectKind::Repeat, true) => result.repeats_hit += 1,
+                (NestedObjectKind::Repeat, false) => result.repeats_missed += 1,
+                (NestedObjectKind::Tail, hit) => result.tail_hit = hit,
+            }
+
+            // a missed tail only withholds its point of combo
+            combo.add(tracking, !matches!(nested.kind, NestedObjectKind::Tail));
+        }
+
+        result.leading_combo = combo.leading.unwrap_or(combo.current);
+        result.trailing_combo = if combo.leading.is_some() {
+            combo.current
+        } else {
+            0
+        };
+        result.max_combo = combo.max;
+
+        Some(result)
+    }
+
+    /// Whether the ball is still tracked at `time`, updating the follow circle with every frame
+    /// since `prev_time`.
+    fn follow(
+        &self,
+        ball_pos: impl Fn(f64) -> Pos2,
+        prev_time: f64,
+        time: f64,
+        mut tracking: bool,
+    ) -> bool {
+        let start = self
+            .frames
+            .partition_point(|frame| f64::from(frame.time) <= prev_time);
+
+        let end = self
+            .frames
+            .partition_point(|frame| f64::from(frame.time) <= time);
+
+        for frame in &self.frames[start..end] {
+            let frame_time = f64::from(frame.time);
+            tracking = self.is_tracking(frame, ball_pos(frame_time), tracking);
+        }
+
+        let Some(frame) = end.checked_sub(1).map(|i| &self.frames[i]) else {
+            return false;
+        };
+
+        self.is_tracking(frame, ball_pos(time), tracking)
+    }
+
+    fn is_tracking(&self, frame: &ReplayFrame, ball_pos: Pos2, was_tracking: bool) -> bool {
+        let radius_sq = if was_tracking {
+            self.radius_sq * Self::FOLLOW_RADIUS_FACTOR * Self::FOLLOW_RADIUS_FACTOR
+        } else {
+            self.radius_sq
+        };
+
+        frame.buttons.is_pressed() && (frame.pos - ball_pos).length_squared() <= radius_sq
+    }
+}
+
+#[derive(Default)]
+struct Combo {
+    current: usize,
+    max: usize,
+    /// Combo before the first break.
+    leading: Option<usize>,
+}
+
+impl Combo {
+    fn add(&mut self, hit: bool, breaks_on_miss: bool) {
+        if hit {
+            self.current += 1;
+            self.max = self.max.max(self.current);
+        } else if breaks_on_miss {
+            self.leading.get_or_insert(self.current);
+            self.current = 0;
+        }
+    }
+}
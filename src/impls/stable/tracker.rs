@@ -0,0 +1,319 @@
+use osu_db::{replay::Action, Mod, ModSet};
+use rosu_pp::{
+    osu::{OsuObject, OsuObjectKind},
+    Beatmap, BeatmapExt, GameMode,
+};
+
+use crate::{
+    error::UrError,
+    error_stats::ErrorStatistics,
+    frames::{Decoded, FrameDecoder, HitFrame, ReplayFrame},
+    judgement::{HitResult, Judgement, SliderResult},
+    options::{TimeScale, UrOptions},
+    report::{HitError, HitErrorReport},
+    spinner::{SpinnerResult, SpinnerSimulator},
+};
+
+use super::{
+    curve::SliderPath,
+    hit_object_manager::HitObjectManager,
+    ruleset::{ClickAction, Ruleset},
+    slider::SliderTracker,
+};
+
+/// Calculates the unstable rate of an osu!standard replay while its frames come in, e.g. from
+/// a spectator stream.
+///
+/// Objects are judged once no later frame can change their result: circles when their 50 hit
+/// window has passed, sliders when they ended, and spinners when they were spun. Once all
+/// frames were pushed, [`UrTracker::finish`] returns the same report as [`StableCalculator`].
+///
+/// [`StableCalculator`]: crate::StableCalculator
+pub struct UrTracker {
+    hit_objects: Vec<OsuObject>,
+    /// Paths of the sliders, `None` for other objects.
+    slider_paths: Vec<Option<SliderPath>>,
+    manager: HitObjectManager,
+    spinner_simulator: SpinnerSimulator,
+    decoder: FrameDecoder,
+    frames: Vec<ReplayFrame>,
+    hit_errors: Vec<HitError>,
+    /// Results of clicked circles and slider heads.
+    clicks: Vec<Option<HitResult>>,
+    /// Time after which each object can be judged, ordered by time.
+    deadlines: Vec<(f64, usize)>,
+    next_deadline: usize,
+    judged: Vec<bool>,
+    judgements: Vec<Judgement>,
+    sliders: Vec<SliderResult>,
+    spinners: Vec<SpinnerResult>,
+    clock_rate: f64,
+    time_scale: TimeScale,
+}
+
+impl UrTracker {
+    /// Track a replay with the given mods on the map.
+    ///
+    /// Returns [`UrError::ModeMismatch`] if the map is not an osu!standard map.
+    pub fn new(map: &Beatmap, mods: ModSet) -> Result<Self, UrError> {
+        Self::with_options(map, mods, &UrOptions::default())
+    }
+
+    /// Track a replay with the given mods on the map using custom [`UrOptions`].
+    ///
+    /// Without a replay to derive it from, the slider notelock fix applies unless
+    /// [`UrOptions::sliderbug_fixed`] says otherwise.
+    pub fn with_options(map: &Beatmap, mods: ModSet, options: &UrOptions) -> Result<Self, UrError> {
+        if map.mode != GameMode::Osu {
+            return Err(UrError::ModeMismatch {
+                replay: GameMode::Osu,
+                map: map.mode,
+            });
+        }
+
+        let map_mods = mods
+            .without(Mod::DoubleTime)
+            .without(Mod::HalfTime)
+            .without(Mod::Nightcore)
+            .bits();
+
+        let hit_objects = map.osu_hitobjects(map_mods);
+        let hard_rock = mods.contains(Mod::HardRock);
+
+        let slider_paths = map
+            .hit_objects
+            .iter()
+            .map(|h| SliderPath::new(h, hard_rock))
+            .collect();
+
+        let sliderbug_fixed = options.sliderbug_fixed.unwrap_or(true);
+        let manager = HitObjectManager::new(&hit_objects, map, map_mods, sliderbug_fixed);
+
+        let mut deadlines: Vec<_> = hit_objects
+            .iter()
+            .enumerate()
+            .map(|(i, h)| (deadline(h, &manager), i))
+            .collect();
+
+        deadlines.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+
+        Ok(Self {
+            clicks: vec![None; hit_objects.len()],
+            judged: vec![false; hit_objects.len()],
+            hit_errors: Vec::with_capacity(hit_objects.len()),
+            judgements: Vec::with_capacity(hit_objects.len()),
+            hit_objects,
+            slider_paths,
+            manager,
+            spinner_simulator: SpinnerSimulator::new(map, mods, &[]),
+            decoder: FrameDecoder::default(),
+            frames: Vec::new(),
+            deadlines,
+            next_deadline: 0,
+            sliders: Vec::new(),
+            spinners: Vec::new(),
+            clock_rate: options.clock_rate_with_mods(mods),
+            time_scale: options.time_scale,
+        })
+    }
+
+    /// Decode and add the next raw replay action, see [`UrTracker::push_frame`].
+    pub fn push_action(&mut self, action: &Action) -> &[Judgement] {
+        match self.decoder.decode(action) {
+            Decoded::Frame(frame) => self.push_frame(frame),
+            Decoded::Seed(_) | Decoded::Skipped => &[],
+        }
+    }
+
+    /// Add the next decoded frame and return the judgements of all objects that could be
+    /// judged because of it, ordered by the time they were judged at.
+    ///
+    /// Frames must be pushed in order of time and their presses must be relative to the
+    /// previous frame, as decoded by [`ReplayFrames`](crate::ReplayFrames).
+    pub fn push_frame(&mut self, frame: ReplayFrame) -> &[Judgement] {
+        let n_judgements = self.judgements.len();
+
+        self.spinner_simulator.push(&frame);
+        self.frames.push(frame);
+
+        for hit_frame in frame.hit_frames() {
+            self.click(&hit_frame);
+        }
+
+        let time = f64::from(frame.time);
+
+        while let Some(&(deadline, i)) = self.deadlines.get(self.next_deadline) {
+            if deadline >= time {
+                break;
+            }
+
+            self.judge(i);
+            self.next_deadline += 1;
+        }
+
+        &self.judgements[n_judgements..]
+    }
+
+    /// Hit errors of all objects that were hit so far, in milliseconds of map time and
+    /// ordered by the time they were hit at.
+    pub fn hit_errors(&self) -> &[HitError] {
+        &self.hit_errors
+    }
+
+    /// Judgements of all objects that were judged so far, ordered by the time they were
+    /// judged at.
+    pub fn judgements(&self) -> &[Judgement] {
+        &self.judgements
+    }
+
+    /// Unstable rate of the hits so far in the options' [`TimeScale`], `0.0` if nothing was
+    /// hit yet.
+    pub fn unstable_rate(&self) -> f64 {
+        if self.hit_errors.is_empty() {
+            return 0.0;
+        }
+
+        let errors: Vec<_> = self
+            .hit_errors
+            .iter()
+            .map(|hit| match self.time_scale {
+                TimeScale::MapTime => hit.error,
+                TimeScale::RealTime => hit.error / self.clock_rate,
+            })
+            .collect();
+
+        ErrorStatistics::new(&errors).unstable_rate
+    }
+
+    /// Judge all remaining objects and return the full [`HitErrorReport`].
+    ///
+    /// Returns [`UrError::NoHitsDetected`] if nothing was hit.
+    pub fn finish(mut self) -> Result<HitErrorReport, UrError> {
+        while let Some(&(_, i)) = self.deadlines.get(self.next_deadline) {
+            self.judge(i);
+            self.next_deadline += 1;
+        }
+
+        let mut judgements = self.judgements;
+        judgements.sort_by_key(|judgement| judgement.index);
+
+        let mut sliders = self.sliders;
+        sliders.sort_by_key(|slider| slider.index);
+
+        let mut spinners = self.spinners;
+        spinners.sort_by_key(|spinner| spinner.index);
+
+        let n_spinners = spinners.len();
+
+        HitErrorReport::new(self.hit_errors, self.hit_objects.len(), n_spinners).map(|report| {
+            report
+                .with_judgements(judgements)
+                .with_sliders(sliders)
+                .with_spinners(spinners)
+                .with_time_scale(self.clock_rate, self.time_scale)
+        })
+    }
+
+    fn click(&mut self, frame: &HitFrame) {
+        let manager = &mut self.manager;
+        manager.update(frame.time);
+
+        let Some((i, h)) = manager.find_circle_at(frame) else {
+            return;
+        };
+
+        match Ruleset::check_click_action(h, i, frame, manager) {
+            ClickAction::Hit => {
+                let index = manager.object_index(i);
+
+                if h.is_normal() {
+                    let accuracy = (frame.time - h.start_time()).abs();
+                    let result = manager.hit_result(accuracy);
+
+                    if result != HitResult::Miss {
+                        self.hit_errors.push(HitError {
+                            index,
+                            error: (frame.time - h.start_time()) as f64,
+                            key: Some(frame.key),
+                            pos: Some(frame.pos),
+                        });
+                    }
+
+                    self.clicks[index] = Some(result);
+                    manager.hit(i);
+
+                    // nothing can change the result of a circle once it's clicked
+                    self.judge(index);
+                } else if h.is_slider() && !h.is_hit {
+                    self.hit_errors.push(HitError {
+                        index,
+                        error: (frame.time - h.start_time()) as f64,
+                        key: Some(frame.key),
+                        pos: Some(frame.pos),
+                    });
+
+                    // the head only counts as hit, sliders are judged once tracked
+                    self.clicks[index] = Some(HitResult::Hit300);
+                    manager.hit(i);
+                }
+            }
+            ClickAction::Ignore | ClickAction::Shake => {}
+        }
+    }
+
+    fn judge(&mut self, index: usize) {
+        if self.judged[index] {
+            return;
+        }
+
+        let h = &self.hit_objects[index];
+        let head_hit = self.clicks[index].is_some();
+
+        let result = match h.kind {
+            OsuObjectKind::Circle => self.clicks[index].unwrap_or(HitResult::Miss),
+            OsuObjectKind::Slider(_) => {
+                let tracker = SliderTracker::new(&self.frames, self.manager.radius_sq);
+
+                let Some(slider) = self.slider_paths[index]
+                    .as_ref()
+                    .and_then(|path| tracker.track(index, h, path, head_hit))
+                else {
+                    return;
+                };
+
+                let result = slider.result();
+                self.sliders.push(slider);
+
+                result
+            }
+            OsuObjectKind::Spinner { .. } => {
+                let Some(spinner) = self.spinner_simulator.simulate(index, h) else {
+                    return;
+                };
+
+                self.spinners.push(spinner);
+
+                spinner.result()
+            }
+        };
+
+        self.judged[index] = true;
+        self.judgements.push(Judgement { index, result });
+    }
+}
+
+/// Time after which no frame can change the object's judgement.
+fn deadline(h: &OsuObject, manager: &HitObjectManager) -> f64 {
+    // circles and slider heads can be hit until their 50 hit window ends
+    let hit_window_end = f64::from(h.start_time as i32 + manager.hit_window_50);
+
+    match h.kind {
+        OsuObjectKind::Circle => hit_window_end,
+        OsuObjectKind::Slider(ref slider) => slider
+            .nested_objects
+            .iter()
+            .map(|nested| nested.start_time)
+            .fold(slider.end_time.max(hit_window_end), f64::max),
+        OsuObjectKind::Spinner { end_time } => end_time,
+    }
+}
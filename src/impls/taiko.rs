@@ -0,0 +1,155 @@
+use osu_db::{Mod, Replay};
+use rosu_pp::{Beatmap, BeatmapExt, GameMode};
+
+use crate::{
+    algorithm::UrCalculator,
+    difficulty::difficulty_range,
+    error::UrError,
+    frames::{Buttons, ReplayFrames},
+    judgement::{HitResult, Judgement},
+    options::UrOptions,
+    report::{HitError, HitErrorReport},
+};
+
+/// Hit detection for osu!taiko, including converted maps.
+pub struct TaikoCalculator;
+
+impl UrCalculator for TaikoCalculator {
+    fn try_calculate_report_with(
+        &self,
+        map: &Beatmap,
+        replay: &Replay,
+        options: &UrOptions,
+    ) -> Result<HitErrorReport, UrError> {
+        try_calculate_report(map, replay)
+            .map(|report| report.with_time_scale(options.clock_rate(replay), options.time_scale))
+    }
+}
+
+pub fn calculate_ur(map: &Beatmap, replay: &Replay) -> f64 {
+    TaikoCalculator.calculate_ur(map, replay)
+}
+
+fn try_calculate_report(map: &Beatmap, replay: &Replay) -> Result<HitErrorReport, UrError> {
+    UrError::check_mode(map, replay, GameMode::Taiko)?;
+
+    let od = if replay.mods.contains(Mod::HardRock) {
+        (map.od * 1.4).min(10.0)
+    } else if replay.mods.contains(Mod::Easy) {
+        map.od / 2.0
+    } else {
+        map.od
+    };
+
+    let od = f64::from(od);
+    let hit_window_300 = difficulty_range(od, 50.0, 35.0, 20.0);
+    let hit_window_100 = difficulty_range(od, 120.0, 80.0, 50.0);
+    let hit_window_miss = difficulty_range(od, 135.0, 95.0, 70.0);
+
+    let presses = DrumPresses::from_replay(replay)?;
+    let hit_objects = map.taiko_hitobjects();
+    let mut hit_errors = Vec::with_capacity(hit_objects.len());
+    let mut results = vec![None; hit_objects.len()];
+
+    // indices of all notes; drum rolls and swells are not judged by timing
+    let notes: Vec<_> = (0..hit_objects.len())
+        .filter(|&i| hit_objects[i].is_hit)
+        .collect();
+
+    let mut next = 0;
+
+    for press in presses {
+        // notes whose window passed without a hit are missed
+        while notes
+            .get(next)
+            .is_some_and(|&i| hit_objects[i].start_time + hit_window_miss < press.time)
+        {
+            next += 1;
+        }
+
+        let Some(&i) = notes.get(next) else {
+            break;
+        };
+
+        let h = &hit_objects[i];
+        let offset = press.time - h.start_time;
+
+        if offset < -hit_window_miss {
+            continue;
+        }
+
+        let result = if press.is_rim != h.is_rim {
+            HitResult::Miss
+        } else if offset.abs() <= hit_window_300 {
+            HitResult::Hit300
+        } else if offset.abs() <= hit_window_100 {
+            HitResult::Hit100
+        } else {
+            HitResult::Miss
+        };
+
+        if result != HitResult::Miss {
+            hit_errors.push(HitError {
+                index: i,
+                error: offset,
+                key: None,
+                pos: None,
+            });
+        }
+
+        results[i] = Some(result);
+        next += 1;
+    }
+
+    let judgements = notes
+        .iter()
+        .map(|&index| Judgement {
+            index,
+            result: results[index].unwrap_or(HitResult::Miss),
+        })
+        .collect();
+
+    let n_skipped = hit_objects.len() - notes.len();
+
+    HitErrorReport::new(hit_errors, hit_objects.len(), n_skipped)
+        .map(|report| report.with_judgements(judgements))
+}
+
+/// A newly pressed drum side.
+///
+/// Pressing both keys of the same colour at once counts as a single press.
+struct DrumPress {
+    time: f64,
+    is_rim: bool,
+}
+
+struct DrumPresses;
+
+impl DrumPresses {
+    // M1: left centre, M2: left rim, K1: right centre, K2: right rim
+    const CENTRE: Buttons = Buttons::M1.union(Buttons::K1);
+    const RIM: Buttons = Buttons::M2.union(Buttons::K2);
+
+    fn from_replay(replay: &Replay) -> Result<Vec<DrumPress>, UrError> {
+        let presses = ReplayFrames::from_replay(replay)?
+            .iter()
+            .flat_map(|frame| {
+                let time = f64::from(frame.time);
+
+                let centre = frame.pressed.intersects(Self::CENTRE).then_some(DrumPress {
+                    time,
+                    is_rim: false,
+                });
+
+                let rim = frame
+                    .pressed
+                    .intersects(Self::RIM)
+                    .then_some(DrumPress { time, is_rim: true });
+
+                centre.into_iter().chain(rim)
+            })
+            .collect();
+
+        Ok(presses)
+    }
+}
@@ -0,0 +1,169 @@
+use osu_db::{Mode, Replay};
+
+/// The result of judging a hit object.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum HitResult {
+    /// osu!mania's MAX judgement, also called rainbow 300.
+    HitMax,
+    Hit300,
+    /// osu!mania's 200 judgement.
+    Hit200,
+    Hit100,
+    Hit50,
+    Miss,
+}
+
+/// The [`HitResult`] of a single hit object.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Judgement {
+    /// Index of the object within the map's hit objects.
+    pub index: usize,
+    pub result: HitResult,
+}
+
+/// How the nested objects of a slider were played.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SliderResult {
+    /// Index of the slider within the map's hit objects.
+    pub index: usize,
+    pub head_hit: bool,
+    pub ticks_hit: usize,
+    pub ticks_missed: usize,
+    pub repeats_hit: usize,
+    pub repeats_missed: usize,
+    pub tail_hit: bool,
+    /// Combo gained from the head up to the first slider break, or from the whole slider if it
+    /// did not break.
+    pub leading_combo: usize,
+    /// Combo gained after the last slider break, `0` if the slider did not break.
+    pub trailing_combo: usize,
+    /// Longest streak of nested objects that were hit in a row.
+    pub max_combo: usize,
+}
+
+impl SliderResult {
+    /// Whether the slider broke combo i.e. its head, a tick, or a repeat was missed.
+    ///
+    /// Missing only the tail loses a point of combo but does not break it.
+    pub fn is_slider_break(&self) -> bool {
+        !self.head_hit || self.ticks_missed > 0 || self.repeats_missed > 0
+    }
+
+    /// The [`HitResult`] of the whole slider based on the ratio of hit nested objects.
+    pub fn result(&self) -> HitResult {
+        let hit = usize::from(self.head_hit)
+            + self.ticks_hit
+            + self.repeats_hit
+            + usize::from(self.tail_hit);
+
+        let total = 2 + self.ticks_hit + self.ticks_missed + self.repeats_hit + self.repeats_missed;
+
+        if hit == total {
+            HitResult::Hit300
+        } else if hit * 2 >= total {
+            HitResult::Hit100
+        } else if hit > 0 {
+            HitResult::Hit50
+        } else {
+            HitResult::Miss
+        }
+    }
+}
+
+/// Kinds of osu!catch objects that are judged by position.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CatchObjectKind {
+    /// Circles as well as slider heads, repeats, and tails.
+    Fruit,
+    /// Slider ticks.
+    Droplet,
+}
+
+/// Whether an osu!catch object landed on the catcher.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct CatchResult {
+    /// Index of the object within the map's catch objects, as returned by
+    /// [`BeatmapExt::catch_hitobjects`](rosu_pp::BeatmapExt::catch_hitobjects).
+    pub index: usize,
+    pub kind: CatchObjectKind,
+    pub caught: bool,
+    /// Distance from the catcher's centre to the object, positive if the object was to the
+    /// right of the catcher.
+    pub offset: f64,
+    /// Whether the catcher was dashing when the object arrived.
+    pub dashing: bool,
+}
+
+impl CatchResult {
+    /// The [`HitResult`] as counted in the replay's header i.e. caught fruits are 300s and
+    /// caught droplets are 100s.
+    pub fn result(&self) -> HitResult {
+        match (self.caught, self.kind) {
+            (true, CatchObjectKind::Fruit) => HitResult::Hit300,
+            (true, CatchObjectKind::Droplet) => HitResult::Hit100,
+            (false, _) => HitResult::Miss,
+        }
+    }
+}
+
+/// Amount of each [`HitResult`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct HitCounts {
+    /// Amount of [`HitResult::HitMax`], always `0` outside of osu!mania.
+    pub n_max: usize,
+    pub n300: usize,
+    /// Amount of [`HitResult::Hit200`], always `0` outside of osu!mania.
+    pub n200: usize,
+    pub n100: usize,
+    pub n50: usize,
+    pub n_miss: usize,
+}
+
+impl HitCounts {
+    /// The counts stored in the replay's header.
+    pub fn from_replay(replay: &Replay) -> Self {
+        // gekis and katus are only judgements in osu!mania
+        let is_mania = replay.mode == Mode::Mania;
+
+        Self {
+            n_max: if is_mania {
+                replay.count_geki as usize
+            } else {
+                0
+            },
+            n300: replay.count_300 as usize,
+            n200: if is_mania {
+                replay.count_katsu as usize
+            } else {
+                0
+            },
+            n100: replay.count_100 as usize,
+            n50: replay.count_50 as usize,
+            n_miss: replay.count_miss as usize,
+        }
+    }
+
+    /// Total amount of judgements.
+    pub fn total(&self) -> usize {
+        self.n_max + self.n300 + self.n200 + self.n100 + self.n50 + self.n_miss
+    }
+}
+
+impl<'j> FromIterator<&'j Judgement> for HitCounts {
+    fn from_iter<I: IntoIterator<Item = &'j Judgement>>(iter: I) -> Self {
+        let mut counts = Self::default();
+
+        for judgement in iter {
+            match judgement.result {
+                HitResult::HitMax => counts.n_max += 1,
+                HitResult::Hit300 => counts.n300 += 1,
+                HitResult::Hit200 => counts.n200 += 1,
+                HitResult::Hit100 => counts.n100 += 1,
+                HitResult::Hit50 => counts.n50 += 1,
+                HitResult::Miss => counts.n_miss += 1,
+            }
+        }
+
+        counts
+    }
+}
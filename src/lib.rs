@@ -1,37 +1,91 @@
+mod aim;
+mod algorithm;
+mod batch;
+mod beatmap_index;
+mod consistency;
+mod difficulty;
+mod error;
+mod error_stats;
+mod frames;
 mod impls;
-mod models;
+mod judgement;
+mod options;
+mod report;
+mod spinner;
+mod tapping;
+mod validation;
 
 use osu_db::Replay;
 use rosu_pp::Beatmap;
 
+pub use aim::{AimOffset, AimReport, AimStatistics};
+pub use algorithm::{Algorithm, AnyModeCalculator, UrCalculator};
+pub use batch::{calculate_batch, find_replays, BatchEntry, BatchError, BatchOptions};
+pub use beatmap_index::{beatmap_hash, verify_beatmap, BeatmapIndex};
+pub use consistency::{ConsistencyReport, SectionSplit, UrSegment, Window};
+pub use error::UrError;
+pub use error_stats::ErrorStatistics;
+pub use frames::{Buttons, Key, KeyPress, ReplayFrame, ReplayFrames};
 pub use impls::{
-    baseline::calculate_ur as calculate_ur_baseline,
-    circleguard::calculate_ur as calculate_ur_circleguard,
-    custom1::calculate_ur as calculate_ur_custom1, custom2::calculate_ur as calculate_ur_custom2,
-    iters::calculate_ur as calculate_ur_iters, stable::calculate_ur as calculate_ur_stable,
+    baseline::{calculate_ur as calculate_ur_baseline, BaselineCalculator},
+    catch::CatchCalculator,
+    circleguard::{calculate_ur as calculate_ur_circleguard, CircleguardCalculator},
+    custom1::{calculate_ur as calculate_ur_custom1, Custom1Calculator},
+    custom2::{calculate_ur as calculate_ur_custom2, Custom2Calculator},
+    iters::{calculate_ur as calculate_ur_iters, ItersCalculator},
+    lazer::{calculate_ur as calculate_ur_lazer, HitPolicy, LazerCalculator},
+    mania::{calculate_ur as calculate_ur_mania, ManiaCalculator},
+    stable::{calculate_ur as calculate_ur_stable, StableCalculator, UrTracker},
+    taiko::{calculate_ur as calculate_ur_taiko, TaikoCalculator},
 };
+pub use judgement::{CatchObjectKind, CatchResult, HitCounts, HitResult, Judgement, SliderResult};
+pub use options::{TimeScale, UrOptions};
+pub use report::{ColumnReport, HitError, HitErrorReport};
+pub use spinner::{simulate_spinners, SpinnerResult};
+pub use tapping::{Tap, TappingReport};
+pub use validation::{validate, validate_with, CountDiff, Validation};
 
-macro_rules! default_fn {
-    ( $( $feature:literal -> $module:ident ,)* ) => {
-        $(
-            #[cfg(feature = $feature)]
-            pub fn calculate_ur(map: &Beatmap, replay: &Replay) -> f64 {
-                impls::$module::calculate_ur(map, replay)
-            }
-        )*
+/// Calculate the unstable rate of the replay on the map, using the given [`Algorithm`] for
+/// osu!standard replays and the calculator of the replay's mode otherwise.
+///
+/// See [`AnyModeCalculator`] for how the mode is handled.
+///
+/// # Panics
+///
+/// Panics if the calculation fails, see [`UrCalculator::try_calculate_ur`].
+pub fn calculate_ur_with(algorithm: Algorithm, map: &Beatmap, replay: &Replay) -> f64 {
+    AnyModeCalculator { algorithm }.calculate_ur(map, replay)
+}
+
+/// Calculate the unstable rate of the replay on the map using the calculator of the replay's
+/// mode and the default [`Algorithm`] for osu!standard.
+///
+/// # Panics
+///
+/// Panics if the calculation fails, see [`try_calculate_ur`].
+pub fn calculate_ur(map: &Beatmap, replay: &Replay) -> f64 {
+    AnyModeCalculator::default().calculate_ur(map, replay)
+}
+
+/// Calculate the [`HitErrorReport`] of the replay on the map using the calculator of the
+/// replay's mode and the default [`Algorithm`] for osu!standard.
+///
+/// # Panics
+///
+/// Panics if the calculation fails, see [`try_calculate_report`].
+pub fn calculate_report(map: &Beatmap, replay: &Replay) -> HitErrorReport {
+    AnyModeCalculator::default().calculate_report(map, replay)
+}
 
-        #[cfg(not(any($( feature = $feature, )*)))]
-        pub fn calculate_ur(map: &Beatmap, replay: &Replay) -> f64 {
-            impls::custom2::calculate_ur(map, replay)
-        }
-    }
+/// Calculate the unstable rate of the replay on the map using the calculator of the replay's
+/// mode and the default [`Algorithm`] for osu!standard, or return why it couldn't be calculated.
+pub fn try_calculate_ur(map: &Beatmap, replay: &Replay) -> Result<f64, UrError> {
+    AnyModeCalculator::default().try_calculate_ur(map, replay)
 }
 
-default_fn! {
-    "baseline" -> baseline,
-    "iters" -> iters,
-    "stable" -> stable,
-    "circleguard" -> circleguard,
-    "custom1" -> custom1,
-    "custom2" -> custom2,
+/// Calculate the [`HitErrorReport`] of the replay on the map using the calculator of the
+/// replay's mode and the default [`Algorithm`] for osu!standard, or return why it couldn't be
+/// calculated.
+pub fn try_calculate_report(map: &Beatmap, replay: &Replay) -> Result<HitErrorReport, UrError> {
+    AnyModeCalculator::default().try_calculate_report(map, replay)
 }
@@ -1,16 +1,882 @@
-use osu_db::Replay;
+use std::{
+    fmt::{Display, Formatter, Result as FmtResult},
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use clap::{
+    builder::PossibleValuesParser, value_parser, Arg, ArgAction, ArgGroup, ArgMatches, Command,
+};
+use osu_db::{Mod, ModSet, Replay};
 use rosu_pp::Beatmap;
-use rosu_ur_calc::calculate_ur;
+use rosu_ur_calc::{
+    calculate_batch, find_replays, verify_beatmap, Algorithm, AnyModeCalculator, BatchEntry,
+    BatchOptions, BeatmapIndex, HitCounts, HitErrorReport, Key, TimeScale, UrCalculator, UrError,
+    UrOptions,
+};
+use serde_json::{json, Map, Value};
+
+/// Mod acronyms indexed by the bit offset of the mod.
+const MOD_ACRONYMS: [&str; 29] = [
+    "NF", "EZ", "TD", "HD", "HR", "SD", "DT", "RX", "HT", "NC", "FL", "AT", "SO", "AP", "PF", "4K",
+    "5K", "6K", "7K", "8K", "FI", "RD", "CN", "TP", "9K", "CO", "1K", "3K", "2K",
+];
+
+const EXIT_CODES: &str = "\
+EXIT CODES:
+    0    Success
+    1    An input could not be read or the output could not be written
+    2    Invalid arguments
+    3    The unstable rate could not be calculated, e.g. because the map does not match the replay";
+
+/// Usage of a subcommand that takes a map and a replay, or a replay and `--osu`.
+macro_rules! replay_usage {
+    ($name:literal) => {
+        concat!(
+            env!("CARGO_PKG_NAME"),
+            " ",
+            $name,
+            " [OPTIONS] <map> <replay>\n    ",
+            env!("CARGO_PKG_NAME"),
+            " ",
+            $name,
+            " [OPTIONS] --osu <osu> <replay>",
+        )
+    };
+}
+
+fn main() -> ExitCode {
+    let matches = command().get_matches();
+
+    let result = match matches.subcommand() {
+        Some(("ur", args)) => ur(args),
+        Some(("report", args)) => report(args),
+        Some(("compare", args)) => compare(args),
+        Some(("batch", args)) => batch(args),
+        _ => unreachable!("a subcommand is required"),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+
+            err.exit_code()
+        }
+    }
+}
+
+fn command() -> Command<'static> {
+    Command::new(env!("CARGO_PKG_NAME"))
+        .version(env!("CARGO_PKG_VERSION"))
+        .about("Calculate the unstable rate of osu! replays")
+        .after_help(EXIT_CODES)
+        .subcommand_required(true)
+        .arg_required_else_help(true)
+        .subcommand(
+            Command::new("ur")
+                .override_usage(replay_usage!("ur"))
+                .allow_missing_positional(true)
+                .about("Print the unstable rate of a replay")
+                .args(common_args())
+                .group(beatmap_group())
+                .arg(algorithm_arg()),
+        )
+        .subcommand(
+            Command::new("report")
+                .override_usage(replay_usage!("report"))
+                .allow_missing_positional(true)
+                .about("Print hit error statistics and judgements of a replay")
+                .args(common_args())
+                .group(beatmap_group())
+                .arg(algorithm_arg()),
+        )
+        .subcommand(
+            Command::new("compare")
+                .override_usage(replay_usage!("compare"))
+                .allow_missing_positional(true)
+                .about("Print the unstable rate of a replay for each algorithm")
+                .args(common_args())
+                .group(beatmap_group()),
+        )
+        .subcommand(
+            Command::new("batch")
+                .about("Calculate every replay of a directory on the beatmap it was played on")
+                .arg(
+                    Arg::new("replays")
+                        .help("Directory to search for .osr files")
+                        .required(true)
+                        .value_parser(value_parser!(PathBuf)),
+                )
+                .arg(
+                    Arg::new("songs")
+                        .long("songs")
+                        .help("Directory to search for .osu files, e.g. osu!'s Songs folder")
+                        .takes_value(true)
+                        .value_parser(value_parser!(PathBuf)),
+                )
+                .arg(
+                    Arg::new("index")
+                        .long("index")
+                        .help("File with one `<md5> <path>` line per beatmap")
+                        .takes_value(true)
+                        .value_parser(value_parser!(PathBuf)),
+                )
+                .arg(
+                    Arg::new("osu")
+                        .long("osu")
+                        .help("osu! directory to look up beatmaps in through its osu!.db")
+                        .takes_value(true)
+                        .value_parser(value_parser!(PathBuf)),
+                )
+                .group(
+                    ArgGroup::new("beatmaps")
+                        .args(&["songs", "index", "osu"])
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("save-index")
+                        .long("save-index")
+                        .help("Write the beatmaps found in --songs to an index file for reuse")
+                        .takes_value(true)
+                        .requires("songs")
+                        .value_parser(value_parser!(PathBuf)),
+                )
+                .arg(
+                    Arg::new("threads")
+                        .short('j')
+                        .long("threads")
+                        .help("Amount of worker threads [default: available parallelism]")
+                        .takes_value(true)
+                        .value_parser(value_parser!(u64).range(1..)),
+                )
+                .arg(
+                    Arg::new("output")
+                        .short('o')
+                        .long("output")
+                        .help("Write the summary to a file instead of stdout")
+                        .takes_value(true)
+                        .value_parser(value_parser!(PathBuf)),
+                )
+                .arg(map_time_arg())
+                .arg(allow_modified_arg())
+                .arg(format_arg(&["csv", "json"]))
+                .arg(algorithm_arg()),
+        )
+}
+
+fn common_args() -> [Arg<'static>; 7] {
+    [
+        // the map may be left out for --osu, in which case the only path is the replay
+        Arg::new("map")
+            .help("Path to the .osu file")
+            .value_parser(value_parser!(PathBuf)),
+        Arg::new("replay")
+            .help("Path to the .osr file")
+            .required(true)
+            .value_parser(value_parser!(PathBuf)),
+        Arg::new("osu")
+            .long("osu")
+            .help("osu! directory to look up the replay's beatmap in through its osu!.db")
+            .takes_value(true)
+            .value_parser(value_parser!(PathBuf)),
+        Arg::new("mods")
+            .short('m')
+            .long("mods")
+            .help("Override the replay's mods, e.g. HDDT or NM")
+            .takes_value(true)
+            .value_parser(parse_mods),
+        map_time_arg(),
+        allow_modified_arg(),
+        format_arg(&["text", "json", "csv"]),
+    ]
+}
+
+/// The beatmap is either given directly or looked up in an osu! directory.
+fn beatmap_group() -> ArgGroup<'static> {
+    ArgGroup::new("beatmap")
+        .args(&["map", "osu"])
+        .required(true)
+}
+
+fn algorithm_arg() -> Arg<'static> {
+    Arg::new("algorithm")
+        .short('a')
+        .long("algorithm")
+        .help("Algorithm for osu!standard replays")
+        .value_parser(PossibleValuesParser::new(
+            Algorithm::ALL.map(Algorithm::name),
+        ))
+        .default_value(Algorithm::default().name())
+}
+
+fn map_time_arg() -> Arg<'static> {
+    Arg::new("map-time")
+        .long("map-time")
+        .help("Report hit errors in map time instead of real time, ignoring rate changing mods")
+        .action(ArgAction::SetTrue)
+}
+
+fn allow_modified_arg() -> Arg<'static> {
+    Arg::new("allow-modified")
+        .long("allow-modified")
+        .help("Calculate even if the map's hash does not match the replay's beatmap hash")
+        .action(ArgAction::SetTrue)
+}
+
+/// Output format argument that defaults to the first of the formats.
+fn format_arg(formats: &'static [&'static str]) -> Arg<'static> {
+    Arg::new("format")
+        .short('f')
+        .long("format")
+        .help("Output format")
+        .value_parser(PossibleValuesParser::new(formats))
+        .default_value(formats[0])
+}
+
+fn ur(args: &ArgMatches) -> Result<(), CliError> {
+    let input = Input::from_args(args)?;
+    let algorithm = algorithm(args);
+
+    let unstable_rate = AnyModeCalculator { algorithm }
+        .try_calculate_report_with(&input.map, &input.replay, &input.options)?
+        .unstable_rate;
+
+    match format(args) {
+        Format::Text => println!("UR: {unstable_rate:.2}"),
+        format => {
+            let mut record = Map::new();
+            record.insert("algorithm".into(), json!(algorithm.name()));
+            record.insert("unstable_rate".into(), json!(unstable_rate));
+
+            write_records(io::stdout().lock(), format, &[record], false)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn report(args: &ArgMatches) -> Result<(), CliError> {
+    let input = Input::from_args(args)?;
+    let algorithm = algorithm(args);
+
+    let report = AnyModeCalculator { algorithm }.try_calculate_report_with(
+        &input.map,
+        &input.replay,
+        &input.options,
+    )?;
+
+    let record = report_record(algorithm, &input.replay, &report);
+
+    match format(args) {
+        Format::Text => print_report(&record),
+        format => write_records(io::stdout().lock(), format, &[record], false)?,
+    }
+
+    Ok(())
+}
+
+fn compare(args: &ArgMatches) -> Result<(), CliError> {
+    let input = Input::from_args(args)?;
+    let mut last_err = None;
+
+    let records: Vec<_> = Algorithm::ALL
+        .into_iter()
+        .map(|algorithm| {
+            let mut record = Map::new();
+            record.insert("algorithm".into(), json!(algorithm.name()));
+
+            let result =
+                algorithm.try_calculate_report_with(&input.map, &input.replay, &input.options);
+
+            let (report, error) = match result {
+                Ok(report) => (Some(report), None),
+                Err(err) => {
+                    let error = err.to_string();
+                    last_err = Some(err);
+
+                    (None, Some(error))
+                }
+            };
+
+            let counts = report.as_ref().and_then(HitErrorReport::hit_counts);
+
+            record.insert(
+                "unstable_rate".into(),
+                json!(report.as_ref().map(|report| report.unstable_rate)),
+            );
+            record.insert(
+                "n_hits".into(),
+                json!(report.as_ref().map(|report| report.hit_errors.len())),
+            );
+            insert_hit_counts(&mut record, counts);
+            record.insert("error".into(), json!(error));
+
+            record
+        })
+        .collect();
+
+    match format(args) {
+        Format::Text => print_comparison(&records, &HitCounts::from_replay(&input.replay)),
+        format => write_records(io::stdout().lock(), format, &records, true)?,
+    }
+
+    let all_failed = records.iter().all(|record| record["error"] != Value::Null);
+
+    match last_err {
+        Some(err) if all_failed => Err(err.into()),
+        _ => Ok(()),
+    }
+}
+
+fn batch(args: &ArgMatches) -> Result<(), CliError> {
+    let replay_dir = args
+        .get_one::<PathBuf>("replays")
+        .expect("replays is required");
+
+    let index = match (
+        args.get_one::<PathBuf>("songs"),
+        args.get_one::<PathBuf>("index"),
+        args.get_one::<PathBuf>("osu"),
+    ) {
+        (Some(songs_dir), ..) => {
+            BeatmapIndex::from_songs_dir(songs_dir).map_err(|err| CliError::read(songs_dir, err))?
+        }
+        (_, Some(index_path), _) => BeatmapIndex::from_index_file(index_path)
+            .map_err(|err| CliError::read(index_path, err))?,
+        (.., Some(osu_dir)) => read_osu_db(osu_dir)?,
+        (None, None, None) => unreachable!("songs, index, or osu is required"),
+    };
+
+    if let Some(index_path) = args.get_one::<PathBuf>("save-index") {
+        let file = File::create(index_path).map_err(|err| CliError::write(index_path, err))?;
+
+        index
+            .write_index(BufWriter::new(file))
+            .map_err(|err| CliError::write(index_path, err))?;
+    }
+
+    let replay_paths = find_replays(replay_dir).map_err(|err| CliError::read(replay_dir, err))?;
+    let algorithm = algorithm(args);
+
+    let options = BatchOptions {
+        options: options(args),
+        threads: args
+            .get_one::<u64>("threads")
+            .map(|&threads| threads as usize),
+        allow_modified_beatmaps: args.get_flag("allow-modified"),
+    };
+
+    let entries = calculate_batch(
+        &AnyModeCalculator { algorithm },
+        &replay_paths,
+        &index,
+        &options,
+    );
+
+    let records: Vec<_> = entries
+        .iter()
+        .map(|entry| batch_record(algorithm, entry))
+        .collect();
+
+    let format = format(args);
+
+    match args.get_one::<PathBuf>("output") {
+        Some(path) => {
+            let file = File::create(path).map_err(|err| CliError::write(path, err))?;
+
+            write_records(BufWriter::new(file), format, &records, true)
+                .map_err(|err| CliError::write(path, err))?;
+        }
+        None => write_records(io::stdout().lock(), format, &records, true)?,
+    }
+
+    let n_failed = entries.iter().filter(|entry| entry.result.is_err()).count();
+
+    eprintln!(
+        "calculated {} of {} replays, {n_failed} failed",
+        entries.len() - n_failed,
+        entries.len()
+    );
+
+    Ok(())
+}
+
+struct Input {
+    map: Beatmap,
+    replay: Replay,
+    options: UrOptions,
+}
+
+impl Input {
+    fn from_args(args: &ArgMatches) -> Result<Self, CliError> {
+        let replay_path = args
+            .get_one::<PathBuf>("replay")
+            .expect("replay is required");
+
+        let mut replay =
+            Replay::from_file(replay_path).map_err(|err| CliError::read(replay_path, err))?;
+
+        let map_path = match (
+            args.get_one::<PathBuf>("map"),
+            args.get_one::<PathBuf>("osu"),
+        ) {
+            (Some(map_path), _) => map_path.to_owned(),
+            (None, Some(osu_dir)) => read_osu_db(osu_dir)?
+                .find(&replay)
+                .map(Path::to_owned)
+                .ok_or_else(|| CliError::BeatmapNotFound(replay.beatmap_hash.clone()))?,
+            (None, None) => unreachable!("map or osu is required"),
+        };
+
+        let bytes = fs::read(&map_path).map_err(|err| CliError::read(&map_path, err))?;
+
+        if !args.get_flag("allow-modified") {
+            verify_beatmap(&bytes, &replay)?;
+        }
+
+        let map = Beatmap::from_bytes(&bytes).map_err(|err| CliError::read(&map_path, err))?;
+
+        if let Some(&mods) = args.get_one::<ModSet>("mods") {
+            replay.mods = mods;
+        }
+
+        Ok(Self {
+            map,
+            replay,
+            options: options(args),
+        })
+    }
+}
+
+fn read_osu_db(osu_dir: &Path) -> Result<BeatmapIndex, CliError> {
+    BeatmapIndex::from_osu_dir(osu_dir).map_err(|err| CliError::read(&osu_dir.join("osu!.db"), err))
+}
+
+fn options(args: &ArgMatches) -> UrOptions {
+    let time_scale = if args.get_flag("map-time") {
+        TimeScale::MapTime
+    } else {
+        TimeScale::RealTime
+    };
+
+    UrOptions {
+        time_scale,
+        ..Default::default()
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+fn format(args: &ArgMatches) -> Format {
+    match args.get_one::<String>("format").map(String::as_str) {
+        Some("json") => Format::Json,
+        Some("csv") => Format::Csv,
+        _ => Format::Text,
+    }
+}
+
+fn algorithm(args: &ArgMatches) -> Algorithm {
+    args.get_one::<String>("algorithm")
+        .and_then(|name| Algorithm::from_name(name))
+        .unwrap_or_default()
+}
+
+/// Parse concatenated mod acronyms such as `HDDT`, optionally prefixed with `+`.
+fn parse_mods(s: &str) -> Result<ModSet, String> {
+    let acronyms = s.trim_start_matches('+').to_ascii_uppercase();
+
+    if acronyms == "NM" {
+        return Ok(ModSet::empty());
+    }
+
+    if acronyms.is_empty() || acronyms.len() % 2 != 0 || !acronyms.is_ascii() {
+        return Err(format!("expected mod acronyms such as HDDT, got `{s}`"));
+    }
+
+    let mut mods = ModSet::empty();
+
+    for i in (0..acronyms.len()).step_by(2) {
+        let acronym = &acronyms[i..i + 2];
+
+        let m = MOD_ACRONYMS
+            .iter()
+            .position(|&known| known == acronym)
+            .and_then(|bit| Mod::from_raw(bit as u8))
+            .ok_or_else(|| format!("unknown mod `{acronym}`"))?;
+
+        mods = mods.with(m);
 
-fn main() {
-    let args: Vec<String> = std::env::args().collect();
-    let map_path = args[1].to_string();
-    let replay_path = args[2].to_string();
+        // the game always sets the mods that these imply
+        match m {
+            Mod::Nightcore => mods = mods.with(Mod::DoubleTime),
+            Mod::Perfect => mods = mods.with(Mod::SuddenDeath),
+            _ => {}
+        }
+    }
 
-    let map = Beatmap::from_path(map_path).unwrap();
-    let replay = Replay::from_file(replay_path).unwrap();
+    Ok(mods)
+}
+
+fn mod_acronyms(mods: ModSet) -> String {
+    let acronyms: String = (0..MOD_ACRONYMS.len() as u8)
+        .filter_map(Mod::from_raw)
+        .filter(|&m| mods.contains(m))
+        .filter(|&m| match m {
+            Mod::DoubleTime => !mods.contains(Mod::Nightcore),
+            Mod::SuddenDeath => !mods.contains(Mod::Perfect),
+            _ => true,
+        })
+        .map(|m| MOD_ACRONYMS[m.raw() as usize])
+        .collect();
+
+    if acronyms.is_empty() {
+        "NM".to_owned()
+    } else {
+        acronyms
+    }
+}
+
+fn batch_record(algorithm: Algorithm, entry: &BatchEntry) -> Map<String, Value> {
+    let mut record = Map::new();
+    let replay = entry.replay.as_ref();
+    let report = entry.result.as_ref().ok();
+
+    record.insert(
+        "replay".into(),
+        json!(entry.replay_path.display().to_string()),
+    );
+    record.insert(
+        "player".into(),
+        json!(replay.and_then(|replay| replay.player_name.as_deref())),
+    );
+    record.insert(
+        "beatmap_hash".into(),
+        json!(replay.and_then(|replay| replay.beatmap_hash.as_deref())),
+    );
+    record.insert(
+        "map".into(),
+        json!(entry
+            .map_path
+            .as_ref()
+            .map(|path| path.display().to_string())),
+    );
+    record.insert("algorithm".into(), json!(algorithm.name()));
+    record.insert(
+        "mode".into(),
+        json!(replay.map(|replay| format!("{:?}", replay.mode))),
+    );
+    record.insert(
+        "mods".into(),
+        json!(replay.map(|replay| mod_acronyms(replay.mods))),
+    );
+    record.insert(
+        "unstable_rate".into(),
+        json!(report.map(|report| report.unstable_rate)),
+    );
+    record.insert(
+        "n_hits".into(),
+        json!(report.map(|report| report.hit_errors.len())),
+    );
+    insert_hit_counts(&mut record, report.and_then(HitErrorReport::hit_counts));
+    record.insert(
+        "error".into(),
+        json!(entry.result.as_ref().err().map(ToString::to_string)),
+    );
+
+    record
+}
+
+fn report_record(
+    algorithm: Algorithm,
+    replay: &Replay,
+    report: &HitErrorReport,
+) -> Map<String, Value> {
+    let mut record = Map::new();
+
+    let time_scale = match report.time_scale {
+        TimeScale::MapTime => "map",
+        TimeScale::RealTime => "real",
+    };
+
+    record.insert("algorithm".into(), json!(algorithm.name()));
+    record.insert("mode".into(), json!(format!("{:?}", replay.mode)));
+    record.insert("mods".into(), json!(mod_acronyms(replay.mods)));
+    record.insert("clock_rate".into(), json!(report.clock_rate));
+    record.insert("time_scale".into(), json!(time_scale));
+    record.insert("unstable_rate".into(), json!(report.unstable_rate));
+    record.insert("mean".into(), json!(report.mean));
+    record.insert("minus_avg".into(), json!(report.minus_avg));
+    record.insert("plus_avg".into(), json!(report.plus_avg));
+    record.insert("minus_max".into(), json!(report.minus_max));
+    record.insert("plus_max".into(), json!(report.plus_max));
+    record.insert("n_hits".into(), json!(report.hit_errors.len()));
+    record.insert("n_judged".into(), json!(report.n_judged));
+    record.insert("n_missed".into(), json!(report.n_missed));
+    record.insert("n_skipped".into(), json!(report.n_skipped));
+    insert_hit_counts(&mut record, report.hit_counts());
+
+    for key in Key::ALL {
+        let name = format!("{key:?}").to_ascii_lowercase();
+        let n_hits = report
+            .hit_errors
+            .iter()
+            .filter(|hit_error| hit_error.key == Some(key))
+            .count();
+
+        // keys without hits would only show zeroed statistics
+        let unstable_rate = (n_hits > 0).then(|| report.key_statistics(key).unstable_rate);
+
+        record.insert(format!("{name}_hits"), json!(n_hits));
+        record.insert(format!("{name}_unstable_rate"), json!(unstable_rate));
+    }
+
+    record
+}
+
+/// Insert the counts as columns that are `null` if judgements were not simulated.
+fn insert_hit_counts(record: &mut Map<String, Value>, counts: Option<HitCounts>) {
+    record.insert("n_max".into(), json!(counts.map(|counts| counts.n_max)));
+    record.insert("n300".into(), json!(counts.map(|counts| counts.n300)));
+    record.insert("n200".into(), json!(counts.map(|counts| counts.n200)));
+    record.insert("n100".into(), json!(counts.map(|counts| counts.n100)));
+    record.insert("n50".into(), json!(counts.map(|counts| counts.n50)));
+    record.insert("n_miss".into(), json!(counts.map(|counts| counts.n_miss)));
+}
 
-    let unstable_rate = calculate_ur(&map, &replay);
+/// Write the records as JSON or as CSV with a header row.
+///
+/// A single record is written as a JSON object unless `array` is set.
+fn write_records(
+    mut writer: impl Write,
+    format: Format,
+    records: &[Map<String, Value>],
+    array: bool,
+) -> io::Result<()> {
+    match format {
+        Format::Json if array || records.len() != 1 => {
+            writeln!(writer, "{}", Value::from(records.to_vec()))?
+        }
+        Format::Json => writeln!(writer, "{}", Value::Object(records[0].clone()))?,
+        Format::Csv | Format::Text => {
+            let Some(first) = records.first() else {
+                return Ok(());
+            };
+
+            let header: Vec<_> = first.keys().map(|key| csv_field(key)).collect();
+            writeln!(writer, "{}", header.join(","))?;
+
+            for record in records {
+                let row: Vec<_> = record
+                    .values()
+                    .map(|value| match value {
+                        Value::Null => String::new(),
+                        Value::String(s) => csv_field(s),
+                        value => value.to_string(),
+                    })
+                    .collect();
+
+                writeln!(writer, "{}", row.join(","))?;
+            }
+        }
+    }
+
+    writer.flush()
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+fn print_report(record: &Map<String, Value>) {
+    let ms = |key: &str| record[key].as_f64().unwrap_or(0.0);
+
+    println!("Algorithm:     {}", text(&record["algorithm"]));
+    println!("Mode:          {}", text(&record["mode"]));
+    println!("Mods:          {}", text(&record["mods"]));
+    println!(
+        "Clock rate:    {} ({} time)",
+        record["clock_rate"],
+        text(&record["time_scale"])
+    );
+    println!("Unstable rate: {:.2}", ms("unstable_rate"));
+    println!("Mean error:    {:+.2}ms", ms("mean"));
+    println!(
+        "Error:         {:.2}ms - {:+.2}ms avg",
+        ms("minus_avg"),
+        ms("plus_avg")
+    );
+    println!(
+        "Error range:   {:.2}ms - {:+.2}ms",
+        ms("minus_max"),
+        ms("plus_max")
+    );
+    println!(
+        "Hits:          {} ({} judged, {} missed, {} skipped)",
+        record["n_hits"], record["n_judged"], record["n_missed"], record["n_skipped"]
+    );
+
+    if record["n300"] != Value::Null {
+        println!("Judgements:    {}", hit_counts_text(record));
+    }
+
+    for key in Key::ALL {
+        let name = format!("{key:?}").to_ascii_lowercase();
+
+        if let Some(unstable_rate) = record[&format!("{name}_unstable_rate")].as_f64() {
+            println!(
+                "{key:?}:            {unstable_rate:.2} UR over {} hits",
+                record[&format!("{name}_hits")]
+            );
+        }
+    }
+}
+
+fn print_comparison(records: &[Map<String, Value>], replay_counts: &HitCounts) {
+    println!(
+        "{:<12} {:>10} {:>7}  judgements ({})",
+        "algorithm",
+        "UR",
+        "hits",
+        format_hit_counts(replay_counts)
+    );
+
+    for record in records {
+        let algorithm = text(&record["algorithm"]);
+
+        match record["error"].as_str() {
+            Some(error) => println!("{algorithm:<12} error: {error}"),
+            None => println!(
+                "{algorithm:<12} {:>10.2} {:>7}  {}",
+                record["unstable_rate"].as_f64().unwrap_or(0.0),
+                record["n_hits"].as_u64().unwrap_or(0),
+                hit_counts_text(record)
+            ),
+        }
+    }
+}
+
+fn hit_counts_text(record: &Map<String, Value>) -> String {
+    let count = |key: &str| record[key].as_u64().unwrap_or(0) as usize;
+
+    if record["n300"] == Value::Null {
+        return "-".to_owned();
+    }
+
+    let counts = HitCounts {
+        n_max: count("n_max"),
+        n300: count("n300"),
+        n200: count("n200"),
+        n100: count("n100"),
+        n50: count("n50"),
+        n_miss: count("n_miss"),
+    };
+
+    format_hit_counts(&counts)
+}
+
+fn format_hit_counts(counts: &HitCounts) -> String {
+    if counts.n_max > 0 || counts.n200 > 0 {
+        format!(
+            "{}/{}/{}/{}/{}/{}x",
+            counts.n_max, counts.n300, counts.n200, counts.n100, counts.n50, counts.n_miss
+        )
+    } else {
+        format!(
+            "{}/{}/{}/{}x",
+            counts.n300, counts.n100, counts.n50, counts.n_miss
+        )
+    }
+}
+
+fn text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        value => value.to_string(),
+    }
+}
+
+#[derive(Debug)]
+enum CliError {
+    Read {
+        path: PathBuf,
+        reason: String,
+    },
+    /// The replay's beatmap is not in the osu!.db, or the replay has no beatmap hash.
+    BeatmapNotFound(Option<String>),
+    /// Writing to the file failed, or to stdout if there is no path.
+    Write {
+        path: Option<PathBuf>,
+        reason: String,
+    },
+    Calculate(UrError),
+}
+
+impl CliError {
+    fn read(path: &Path, reason: impl Display) -> Self {
+        Self::Read {
+            path: path.to_owned(),
+            reason: reason.to_string(),
+        }
+    }
+
+    fn write(path: &Path, reason: impl Display) -> Self {
+        Self::Write {
+            path: Some(path.to_owned()),
+            reason: reason.to_string(),
+        }
+    }
+
+    fn exit_code(&self) -> ExitCode {
+        match self {
+            Self::Read { .. } | Self::BeatmapNotFound(_) | Self::Write { .. } => ExitCode::from(1),
+            Self::Calculate(_) => ExitCode::from(3),
+        }
+    }
+}
+
+impl From<io::Error> for CliError {
+    fn from(err: io::Error) -> Self {
+        Self::Write {
+            path: None,
+            reason: err.to_string(),
+        }
+    }
+}
+
+impl From<UrError> for CliError {
+    fn from(err: UrError) -> Self {
+        Self::Calculate(err)
+    }
+}
 
-    println!("UR: {unstable_rate:#?}");
+impl Display for CliError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Read { path, reason } => {
+                write!(f, "failed to read `{}`: {reason}", path.display())
+            }
+            Self::BeatmapNotFound(Some(hash)) => {
+                write!(f, "no beatmap with hash {hash} is in the osu!.db")
+            }
+            Self::BeatmapNotFound(None) => f.write_str("replay does not contain a beatmap hash"),
+            Self::Write {
+                path: Some(path),
+                reason,
+            } => write!(f, "failed to write `{}`: {reason}", path.display()),
+            Self::Write { path: None, reason } => write!(f, "failed to write output: {reason}"),
+            Self::Calculate(err) => write!(f, "failed to calculate the unstable rate: {err}"),
+        }
+    }
 }
@@ -0,0 +1,59 @@
+use osu_db::{Mod, ModSet, Replay};
+
+/// The time base in which hit errors are reported.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum TimeScale {
+    /// Milliseconds of the map's audio; what the calculation works with internally.
+    ///
+    /// Hit errors are not adjusted for rate changing mods so the unstable rate of DoubleTime
+    /// replays is higher than the one osu! shows.
+    MapTime,
+    /// Milliseconds as experienced by the player i.e. map time divided by the clock rate.
+    ///
+    /// This is what osu! shows on its results screen.
+    #[default]
+    RealTime,
+}
+
+/// Settings for an unstable rate calculation.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct UrOptions {
+    /// Overrides the clock rate that is otherwise derived from the replay's mods,
+    /// e.g. for lazer's rate adjust mods.
+    pub clock_rate: Option<f64>,
+    /// The time base of the reported hit errors.
+    pub time_scale: TimeScale,
+    /// Overrides whether the slider notelock fix applies, which is otherwise derived from the
+    /// game version the replay was set on.
+    pub sliderbug_fixed: Option<bool>,
+}
+
+impl UrOptions {
+    /// The first game version in which sliders lock subsequent objects until their 50 hit
+    /// window ends, even if the slider itself ends earlier.
+    pub const SLIDERBUG_FIX_VERSION: u32 = 20190506;
+
+    /// The clock rate the replay was played at.
+    pub fn clock_rate(&self, replay: &Replay) -> f64 {
+        self.clock_rate_with_mods(replay.mods)
+    }
+
+    /// The clock rate of the mods.
+    pub fn clock_rate_with_mods(&self, mods: ModSet) -> f64 {
+        self.clock_rate.unwrap_or_else(|| {
+            if mods.contains(Mod::DoubleTime) || mods.contains(Mod::Nightcore) {
+                1.5
+            } else if mods.contains(Mod::HalfTime) {
+                0.75
+            } else {
+                1.0
+            }
+        })
+    }
+
+    /// Whether the replay was set on a game version with the slider notelock fix.
+    pub fn sliderbug_fixed(&self, replay: &Replay) -> bool {
+        self.sliderbug_fixed
+            .unwrap_or(replay.version >= Self::SLIDERBUG_FIX_VERSION)
+    }
+}
@@ -0,0 +1,319 @@
+use rosu_pp::parse::Pos2;
+
+use crate::{
+    error::UrError,
+    error_stats::ErrorStatistics,
+    frames::Key,
+    judgement::{CatchResult, HitCounts, HitResult, Judgement, SliderResult},
+    options::TimeScale,
+    spinner::SpinnerResult,
+};
+
+/// The timing error of a single judged hit object.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct HitError {
+    /// Index of the object within the map's hit objects.
+    pub index: usize,
+    /// Offset of the hit to the object's start time in milliseconds of the report's
+    /// [`TimeScale`].
+    ///
+    /// Negative values are early hits, positive values are late hits.
+    pub error: f64,
+    /// The key that hit the object.
+    ///
+    /// `None` if the calculation does not attribute hits to keys, e.g. outside of osu!standard.
+    pub key: Option<Key>,
+    /// Position of the cursor when the object was hit.
+    ///
+    /// `None` outside of osu!standard.
+    pub pos: Option<Pos2>,
+}
+
+/// Hit and release errors of a single osu!mania column.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ColumnReport {
+    /// Index of the column, starting at `0` on the left.
+    pub column: usize,
+    /// Hit error of each judged note and hold note head, ordered by object index.
+    pub hit_errors: Vec<HitError>,
+    /// Release error of each released hold note tail, ordered by object index.
+    pub release_errors: Vec<HitError>,
+}
+
+impl ColumnReport {
+    /// Unstable rate of the column's hit errors.
+    pub fn unstable_rate(&self) -> f64 {
+        self.statistics().unstable_rate
+    }
+
+    /// Detailed [`ErrorStatistics`] over the column's hit errors.
+    pub fn statistics(&self) -> ErrorStatistics {
+        let errors: Vec<_> = self.hit_errors.iter().map(|hit| hit.error).collect();
+
+        ErrorStatistics::new(&errors)
+    }
+
+    /// Detailed [`ErrorStatistics`] over the column's release errors.
+    pub fn release_statistics(&self) -> ErrorStatistics {
+        let errors: Vec<_> = self
+            .release_errors
+            .iter()
+            .map(|release| release.error)
+            .collect();
+
+        ErrorStatistics::new(&errors)
+    }
+}
+
+/// Everything the results screen shows about hit errors.
+#[derive(Clone, Debug, Default)]
+pub struct HitErrorReport {
+    /// Standard deviation of the hit errors, multiplied by 10.
+    pub unstable_rate: f64,
+    /// Average hit error across all judged objects.
+    pub mean: f64,
+    /// Average of all early hit errors, `0.0` if there were none.
+    pub minus_avg: f64,
+    /// Average of all late hit errors, `0.0` if there were none.
+    pub plus_avg: f64,
+    /// Earliest hit error, even if it was late, and `i32::MAX` if there were no hit errors.
+    pub minus_max: f64,
+    /// Latest hit error, `0.0` if there were no late hits.
+    pub plus_max: f64,
+    /// Hit error of each judged object, ordered by object index.
+    pub hit_errors: Vec<HitError>,
+    /// Amount of objects that were hit and thus have a hit error.
+    pub n_judged: usize,
+    /// Amount of objects that the calculation does not consider, e.g. spinners.
+    pub n_skipped: usize,
+    /// Amount of considered objects that were not hit.
+    pub n_missed: usize,
+    /// The clock rate the replay was played at.
+    pub clock_rate: f64,
+    /// The time base of all hit errors and values derived from them.
+    pub time_scale: TimeScale,
+    /// Judgement of each object, ordered by object index.
+    ///
+    /// Empty if the calculation does not simulate judgements.
+    pub judgements: Vec<Judgement>,
+    /// Result of each slider's ticks, repeats, and tail, ordered by object index.
+    ///
+    /// Empty if the calculation does not simulate slider tracking.
+    pub sliders: Vec<SliderResult>,
+    /// Result of each spinner, ordered by object index.
+    ///
+    /// Empty if the calculation does not simulate spinning.
+    pub spinners: Vec<SpinnerResult>,
+    /// Hit and release errors of each osu!mania column, ordered by column.
+    ///
+    /// Empty outside of osu!mania.
+    pub columns: Vec<ColumnReport>,
+    /// Result of each osu!catch fruit and droplet, ordered by object index.
+    ///
+    /// Empty outside of osu!catch.
+    pub catches: Vec<CatchResult>,
+    /// Amount of bananas caught from the map's banana showers.
+    ///
+    /// Bananas are bonus objects and thus neither judged nor missed. Always `0` outside of
+    /// osu!catch.
+    pub n_bananas: usize,
+}
+
+impl HitErrorReport {
+    pub(crate) fn new(
+        mut hit_errors: Vec<HitError>,
+        n_objects: usize,
+        n_skipped: usize,
+    ) -> Result<Self, UrError> {
+        if hit_errors.is_empty() {
+            return Err(UrError::NoHitsDetected);
+        }
+
+        let errors: Vec<_> = hit_errors.iter().map(|hit_error| hit_error.error).collect();
+        let stats = ErrorStatistics::new(&errors);
+
+        hit_errors.sort_by_key(|hit_error| hit_error.index);
+
+        let n_judged = hit_errors.len();
+        let n_missed = n_objects.saturating_sub(n_skipped + n_judged);
+
+        Ok(Self {
+            unstable_rate: stats.unstable_rate,
+            mean: stats.avg,
+            minus_avg: stats.minus_avg,
+            plus_avg: stats.plus_avg,
+            minus_max: stats.minus_max,
+            plus_max: stats.plus_max,
+            hit_errors,
+            n_judged,
+            n_skipped,
+            n_missed,
+            clock_rate: 1.0,
+            time_scale: TimeScale::MapTime,
+            judgements: Vec::new(),
+            sliders: Vec::new(),
+            spinners: Vec::new(),
+            columns: Vec::new(),
+            catches: Vec::new(),
+            n_bananas: 0,
+        })
+    }
+
+    /// A report for modes without hit errors such as osu!catch.
+    ///
+    /// Returns [`UrError::NoHitsDetected`] if no object was judged.
+    pub(crate) fn without_hit_errors(
+        n_judged: usize,
+        n_skipped: usize,
+        n_missed: usize,
+    ) -> Result<Self, UrError> {
+        if n_judged == 0 {
+            return Err(UrError::NoHitsDetected);
+        }
+
+        Ok(Self {
+            n_judged,
+            n_skipped,
+            n_missed,
+            clock_rate: 1.0,
+            time_scale: TimeScale::MapTime,
+            ..Default::default()
+        })
+    }
+
+    pub(crate) fn with_judgements(self, judgements: Vec<Judgement>) -> Self {
+        Self { judgements, ..self }
+    }
+
+    pub(crate) fn with_sliders(self, sliders: Vec<SliderResult>) -> Self {
+        Self { sliders, ..self }
+    }
+
+    pub(crate) fn with_spinners(self, spinners: Vec<SpinnerResult>) -> Self {
+        Self { spinners, ..self }
+    }
+
+    pub(crate) fn with_columns(self, columns: Vec<ColumnReport>) -> Self {
+        Self { columns, ..self }
+    }
+
+    pub(crate) fn with_catches(self, catches: Vec<CatchResult>, n_bananas: usize) -> Self {
+        Self {
+            catches,
+            n_bananas,
+            ..self
+        }
+    }
+
+    pub(crate) fn with_time_scale(mut self, clock_rate: f64, time_scale: TimeScale) -> Self {
+        self.clock_rate = clock_rate;
+
+        if time_scale == self.time_scale {
+            return self;
+        }
+
+        for spinner in self.spinners.iter_mut() {
+            *spinner = match time_scale {
+                TimeScale::MapTime => spinner.with_clock_rate(1.0 / clock_rate),
+                TimeScale::RealTime => spinner.with_clock_rate(clock_rate),
+            };
+        }
+
+        // nothing to rescale for modes without hit errors
+        if self.hit_errors.is_empty() {
+            return Self { time_scale, ..self };
+        }
+
+        let column_errors = self.columns.iter_mut().flat_map(|column| {
+            column
+                .hit_errors
+                .iter_mut()
+                .chain(column.release_errors.iter_mut())
+        });
+
+        for hit_error in self.hit_errors.iter_mut().chain(column_errors) {
+            match time_scale {
+                TimeScale::MapTime => hit_error.error *= clock_rate,
+                TimeScale::RealTime => hit_error.error /= clock_rate,
+            }
+        }
+
+        let stats = self.statistics();
+
+        Self {
+            unstable_rate: stats.unstable_rate,
+            mean: stats.avg,
+            minus_avg: stats.minus_avg,
+            plus_avg: stats.plus_avg,
+            minus_max: stats.minus_max,
+            plus_max: stats.plus_max,
+            time_scale,
+            ..self
+        }
+    }
+
+    /// Amount of each judgement, `None` if the calculation does not simulate judgements.
+    pub fn hit_counts(&self) -> Option<HitCounts> {
+        (!self.judgements.is_empty()).then(|| self.judgements.iter().collect())
+    }
+
+    /// The highest combo reached, `None` if the calculation does not simulate judgements.
+    ///
+    /// Every judgement other than a miss continues the combo and a miss breaks it. Sliders
+    /// instead add a point for their head and each nested object that was hit, and break on
+    /// missed heads, ticks, and repeats. Objects are combined in order of their index.
+    pub fn max_combo(&self) -> Option<usize> {
+        if self.judgements.is_empty() {
+            return None;
+        }
+
+        let mut sliders = self.sliders.iter().peekable();
+        let mut combo = 0;
+        let mut max_combo = 0;
+
+        for judgement in self.judgements.iter() {
+            match sliders.next_if(|slider| slider.index == judgement.index) {
+                Some(slider) if slider.is_slider_break() => {
+                    max_combo = max_combo
+                        .max(combo + slider.leading_combo)
+                        .max(slider.max_combo);
+                    combo = slider.trailing_combo;
+                }
+                Some(slider) => combo += slider.leading_combo,
+                None if judgement.result == HitResult::Miss => {
+                    max_combo = max_combo.max(combo);
+                    combo = 0;
+                }
+                None => combo += 1,
+            }
+        }
+
+        Some(max_combo.max(combo))
+    }
+
+    /// Detailed [`ErrorStatistics`] over the hit errors.
+    pub fn statistics(&self) -> ErrorStatistics {
+        let errors: Vec<_> = self
+            .hit_errors
+            .iter()
+            .map(|hit_error| hit_error.error)
+            .collect();
+
+        ErrorStatistics::new(&errors)
+    }
+
+    /// Detailed [`ErrorStatistics`] over the hit errors of objects that were hit with the key.
+    ///
+    /// Compare the [`unstable_rate`](ErrorStatistics::unstable_rate) and
+    /// [`avg`](ErrorStatistics::avg) of each key to see whether one finger is consistently off.
+    pub fn key_statistics(&self, key: Key) -> ErrorStatistics {
+        let errors: Vec<_> = self
+            .hit_errors
+            .iter()
+            .filter(|hit_error| hit_error.key == Some(key))
+            .map(|hit_error| hit_error.error)
+            .collect();
+
+        ErrorStatistics::new(&errors)
+    }
+}
@@ -0,0 +1,256 @@
+use std::f64::consts::{PI, TAU};
+
+use osu_db::{Mod, ModSet, Replay};
+use rosu_pp::{
+    osu::{OsuObject, OsuObjectKind},
+    Beatmap, BeatmapExt,
+};
+
+use crate::{
+    difficulty::difficulty_range,
+    error::UrError,
+    frames::{ReplayFrame, ReplayFrames},
+    judgement::HitResult,
+    options::UrOptions,
+};
+
+/// How a spinner was spun.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct SpinnerResult {
+    /// Index of the spinner within the map's hit objects.
+    pub index: usize,
+    /// Full rotations of the cursor around the playfield centre.
+    pub rotations: f64,
+    /// Rotations needed to clear the spinner.
+    ///
+    /// Unlike the rest of the simulation, this follows osu!lazer's `SpinsRequired` rather
+    /// than stable.
+    pub required_rotations: f64,
+    /// Highest spin speed within any 595ms, as shown in-game.
+    ///
+    /// Spin speeds are in rotations per minute of the report's [`TimeScale`], i.e. they are
+    /// multiplied by the clock rate in real time.
+    ///
+    /// [`TimeScale`]: crate::TimeScale
+    pub max_rpm: f64,
+    /// Spin speed across the whole duration of the spinner.
+    pub avg_rpm: f64,
+    /// Full rotations that exceed the requirement by more than the bonus gap.
+    ///
+    /// Both the requirement and the gap are osu!lazer's.
+    pub bonus_spins: usize,
+}
+
+impl SpinnerResult {
+    /// Whether enough rotations were made to clear the spinner, judged by osu!lazer's
+    /// [`required_rotations`](Self::required_rotations).
+    pub fn is_cleared(&self) -> bool {
+        self.rotations >= self.required_rotations
+    }
+
+    /// The ratio of rotations to required rotations.
+    pub fn progress(&self) -> f64 {
+        if self.required_rotations > 0.0 {
+            self.rotations / self.required_rotations
+        } else {
+            1.0
+        }
+    }
+
+    /// Scale the spin speeds from one time base to another.
+    pub(crate) fn with_clock_rate(self, clock_rate: f64) -> Self {
+        Self {
+            max_rpm: self.max_rpm * clock_rate,
+            avg_rpm: self.avg_rpm * clock_rate,
+            ..self
+        }
+    }
+
+    /// The [`HitResult`] of the spinner based on its progress.
+    pub fn result(&self) -> HitResult {
+        match self.progress() {
+            progress if progress >= 1.0 => HitResult::Hit300,
+            progress if progress > 0.9 => HitResult::Hit100,
+            progress if progress > 0.75 => HitResult::Hit50,
+            _ => HitResult::Miss,
+        }
+    }
+}
+
+/// Simulate each spinner of the map.
+///
+/// Spinning is integrated from the cursor's angle around the playfield centre while a key is
+/// held, or at all times with Relax. SpunOut and Autopilot spin on their own at a fixed speed.
+/// Spin speeds are in real time, the default [`TimeScale`](crate::TimeScale).
+pub fn simulate_spinners(map: &Beatmap, replay: &Replay) -> Result<Vec<SpinnerResult>, UrError> {
+    UrError::check_osu_mode(map, replay)?;
+
+    let mods = replay
+        .mods
+        .without(Mod::DoubleTime)
+        .without(Mod::HalfTime)
+        .without(Mod::Nightcore)
+        .bits();
+
+    let frames = ReplayFrames::from_replay(replay)?;
+    let simulator = SpinnerSimulator::new(map, replay.mods, &frames);
+    let clock_rate = UrOptions::default().clock_rate(replay);
+
+    let spinners = map
+        .osu_hitobjects(mods)
+        .iter()
+        .enumerate()
+        .filter_map(|(index, h)| simulator.simulate(index, h))
+        .map(|spinner| spinner.with_clock_rate(clock_rate))
+        .collect();
+
+    Ok(spinners)
+}
+
+pub(crate) struct SpinnerSimulator {
+    frames: Vec<SpinFrame>,
+    rotations_per_second: f64,
+    auto_spin: bool,
+    needs_keys: bool,
+}
+
+impl SpinnerSimulator {
+    /// Speed of SpunOut and Autopilot, same as autoplay.
+    const AUTO_SPIN_RPM: f64 = 286.0;
+    /// Fastest cursor movement that is counted, roughly 477 RPM.
+    const MAX_RADIANS_PER_MS: f64 = 0.05;
+    /// Duration over which the displayed RPM is measured.
+    const RPM_WINDOW: f64 = 595.0;
+    /// Rotations beyond the requirement before spins count as bonus, as in osu!lazer.
+    const BONUS_SPIN_GAP: f64 = 2.0;
+
+    const CENTRE_X: f32 = 256.0;
+    const CENTRE_Y: f32 = 192.0;
+
+    pub(crate) fn new(map: &Beatmap, mods: ModSet, frames: &[ReplayFrame]) -> Self {
+        let od = if mods.contains(Mod::HardRock) {
+            (map.od * 1.4).min(10.0)
+        } else if mods.contains(Mod::Easy) {
+            map.od / 2.0
+        } else {
+            map.od
+        };
+
+        let mut simulator = Self {
+            frames: Vec::with_capacity(frames.len()),
+            rotations_per_second: difficulty_range(f64::from(od), 1.5, 2.5, 3.75),
+            auto_spin: mods.contains(Mod::SpunOut) || mods.contains(Mod::Autopilot),
+            needs_keys: !mods.contains(Mod::Relax),
+        };
+
+        for frame in frames {
+            simulator.push(frame);
+        }
+
+        simulator
+    }
+
+    /// Add a frame after all previous frames.
+    pub(crate) fn push(&mut self, frame: &ReplayFrame) {
+        self.frames.push(SpinFrame {
+            time: f64::from(frame.time),
+            angle: f64::from(frame.pos.y - Self::CENTRE_Y)
+                .atan2(f64::from(frame.pos.x - Self::CENTRE_X)),
+            pressed: frame.buttons.is_pressed(),
+        });
+    }
+
+    pub(crate) fn simulate(&self, index: usize, h: &OsuObject) -> Option<SpinnerResult> {
+        let OsuObjectKind::Spinner { end_time } = h.kind else {
+            return None;
+        };
+
+        let start_time = h.start_time;
+        let duration = end_time - start_time;
+
+        // unlike the stable-style rotation tracking, the requirement is osu!lazer's
+        /*
+            double secondsDuration = Duration / 1000;
+            double minimumRotationsPerSecond = IBeatmapDifficultyInfo.DifficultyRange(difficulty.OverallDifficulty, 1.5, 2.5, 3.75);
+            SpinsRequired = (int)(secondsDuration * minimumRotationsPerSecond);
+        */
+
+        let required_rotations = (duration / 1000.0 * self.rotations_per_second).trunc();
+
+        // total rotation in radians at the time of each frame
+        let mut progression = vec![(start_time, 0.0)];
+
+        if self.auto_spin {
+            let radians_per_ms = Self::AUTO_SPIN_RPM * TAU / 60_000.0;
+            progression.push((end_time, duration * radians_per_ms));
+        } else {
+            let start = self.frames.partition_point(|frame| frame.time < start_time);
+            let mut total = 0.0;
+            let mut prev: Option<&SpinFrame> = None;
+
+            for frame in self.frames[start..]
+                .iter()
+                .take_while(|frame| frame.time <= end_time)
+            {
+                if let Some(prev) = prev.filter(|_| frame.pressed || !self.needs_keys) {
+                    let mut delta = frame.angle - prev.angle;
+
+                    if delta > PI {
+                        delta -= TAU;
+                    } else if delta < -PI {
+                        delta += TAU;
+                    }
+
+                    let limit = (frame.time - prev.time) * Self::MAX_RADIANS_PER_MS;
+                    total += delta.abs().min(limit);
+                }
+
+                progression.push((frame.time, total));
+                prev = Some(frame);
+            }
+        }
+
+        let total = progression.last().map_or(0.0, |(_, total)| *total);
+        let rotations = total / TAU;
+
+        let mut max_rpm: f64 = 0.0;
+        let mut window_start = 0;
+
+        for &(time, total) in progression.iter() {
+            while progression[window_start].0 < time - Self::RPM_WINDOW {
+                window_start += 1;
+            }
+
+            let (start_time, start_total) = progression[window_start];
+
+            if time > start_time {
+                let rpm = (total - start_total) / TAU / (time - start_time) * 60_000.0;
+                max_rpm = max_rpm.max(rpm);
+            }
+        }
+
+        let avg_rpm = if duration > 0.0 {
+            rotations / duration * 60_000.0
+        } else {
+            0.0
+        };
+
+        let bonus_spins =
+            (rotations.floor() - required_rotations - Self::BONUS_SPIN_GAP).max(0.0) as usize;
+
+        Some(SpinnerResult {
+            index,
+            rotations,
+            required_rotations,
+            max_rpm,
+            avg_rpm,
+            bonus_spins,
+        })
+    }
+}
+
+struct SpinFrame {
+    time: f64,
+    angle: f64,
+    pressed: bool,
+}
@@ -0,0 +1,131 @@
+use osu_db::{Mod, Replay};
+use rosu_pp::{Beatmap, BeatmapExt};
+
+use crate::{
+    error::UrError,
+    error_stats::ErrorStatistics,
+    frames::{Key, KeyPress, ReplayFrames},
+    options::TimeScale,
+    report::HitErrorReport,
+};
+
+/// The key press that hit an object.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Tap {
+    /// Index of the object within the map's hit objects.
+    pub index: usize,
+    pub key: Key,
+    /// Time in ms at which the key was pressed.
+    pub press_time: i32,
+    /// How long the key was held in ms, `None` if it was never released.
+    pub hold_duration: Option<i32>,
+}
+
+/// Tapping technique of an osu!standard replay.
+///
+/// Contains every key press of the replay and, for each hit object of a [`HitErrorReport`],
+/// the press that hit it.
+#[derive(Clone, Debug, Default)]
+pub struct TappingReport {
+    /// Every press of the replay, ordered by press time.
+    pub presses: Vec<KeyPress>,
+    /// The press of each object with a hit error, ordered by object index.
+    pub taps: Vec<Tap>,
+}
+
+impl TappingReport {
+    /// Pair the hit errors of the report with the replay's key presses.
+    ///
+    /// The report must have been calculated on the same map and replay. Each hit is traced
+    /// back to the latest press of its [`HitError::key`](crate::HitError::key) up to the hit
+    /// time. Hit errors without a key are left out of the taps.
+    pub fn new(map: &Beatmap, replay: &Replay, report: &HitErrorReport) -> Result<Self, UrError> {
+        UrError::check_osu_mode(map, replay)?;
+
+        let presses = ReplayFrames::from_replay(replay)?.key_presses();
+
+        // frames are in map time so the objects must be as well
+        let mods = replay
+            .mods
+            .without(Mod::DoubleTime)
+            .without(Mod::HalfTime)
+            .without(Mod::Nightcore);
+
+        let hit_objects = map.osu_hitobjects(mods.bits());
+
+        let to_map_time = match report.time_scale {
+            TimeScale::MapTime => 1.0,
+            TimeScale::RealTime => report.clock_rate,
+        };
+
+        let mut taps = Vec::with_capacity(report.hit_errors.len());
+
+        for hit_error in report.hit_errors.iter() {
+            let (Some(key), Some(h)) = (hit_error.key, hit_objects.get(hit_error.index)) else {
+                continue;
+            };
+
+            // objects are hit on the frame that pressed the key which is at a full ms
+            let hit_time = (h.start_time + hit_error.error * to_map_time).round() as i32;
+            let pressed = presses.partition_point(|press| press.press_time <= hit_time);
+
+            let press = presses[..pressed]
+                .iter()
+                .rev()
+                .find(|press| press.key == key);
+
+            if let Some(press) = press {
+                taps.push(Tap {
+                    index: hit_error.index,
+                    key,
+                    press_time: press.press_time,
+                    hold_duration: press.hold_duration(),
+                });
+            }
+        }
+
+        Ok(Self { presses, taps })
+    }
+
+    /// How often the key was pressed throughout the replay.
+    pub fn press_count(&self, key: Key) -> usize {
+        self.presses.iter().filter(|press| press.key == key).count()
+    }
+
+    /// How many objects were hit with the key.
+    pub fn tap_count(&self, key: Key) -> usize {
+        self.taps.iter().filter(|tap| tap.key == key).count()
+    }
+
+    /// [`ErrorStatistics`] over the hold durations of the taps of the key, or of all taps if
+    /// `key` is `None`.
+    ///
+    /// Taps that were never released are left out.
+    pub fn hold_statistics(&self, key: Option<Key>) -> ErrorStatistics {
+        let durations: Vec<_> = self
+            .taps
+            .iter()
+            .filter(|tap| key.map_or(true, |key| tap.key == key))
+            .filter_map(|tap| tap.hold_duration)
+            .collect();
+
+        ErrorStatistics::new(&durations)
+    }
+
+    /// Fraction of consecutive taps that used a different key than the previous tap.
+    ///
+    /// `1.0` for full alternation, `0.0` for singletapping or if there are less than two taps.
+    pub fn alternation_ratio(&self) -> f64 {
+        if self.taps.len() < 2 {
+            return 0.0;
+        }
+
+        let n_alternations = self
+            .taps
+            .windows(2)
+            .filter(|pair| pair[0].key != pair[1].key)
+            .count();
+
+        n_alternations as f64 / (self.taps.len() - 1) as f64
+    }
+}
@@ -0,0 +1,141 @@
+use osu_db::{Mode, Replay};
+use rosu_pp::Beatmap;
+
+use crate::{
+    algorithm::{Algorithm, AnyModeCalculator, UrCalculator},
+    error::UrError,
+    judgement::HitCounts,
+    options::UrOptions,
+    report::HitErrorReport,
+};
+
+/// A count stored in the replay alongside the count reconstructed by the simulation.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct CountDiff {
+    pub replay: usize,
+    pub simulated: usize,
+}
+
+impl CountDiff {
+    /// How many more the simulation counted than the replay, negative if it counted fewer.
+    pub fn diff(self) -> isize {
+        self.simulated as isize - self.replay as isize
+    }
+
+    /// Whether both counts agree.
+    pub fn matches(self) -> bool {
+        self.replay == self.simulated
+    }
+}
+
+/// Comparison of a simulation's judgements with the counts stored in the replay's header.
+///
+/// osu!catch stores caught tiny droplets as 50s but they are not simulated, so 50s are not
+/// compared for osu!catch replays.
+#[derive(Clone, Debug)]
+pub struct Validation {
+    pub n_max: CountDiff,
+    pub n300: CountDiff,
+    pub n200: CountDiff,
+    pub n100: CountDiff,
+    pub n50: CountDiff,
+    pub n_miss: CountDiff,
+    /// Highest combo, reconstructed from the judgements and slider results.
+    pub max_combo: CountDiff,
+    /// The report whose judgements were compared.
+    pub report: HitErrorReport,
+}
+
+impl Validation {
+    /// Compare the report's judgements with the replay's header.
+    ///
+    /// Returns [`UrError::JudgementsNotSimulated`] if the report has no judgements.
+    pub fn new(report: HitErrorReport, replay: &Replay) -> Result<Self, UrError> {
+        let simulated = report.hit_counts().ok_or(UrError::JudgementsNotSimulated)?;
+        let mut expected = HitCounts::from_replay(replay);
+
+        if replay.mode == Mode::CatchTheBeat {
+            expected.n50 = 0;
+        }
+
+        let max_combo = report.max_combo().ok_or(UrError::JudgementsNotSimulated)?;
+
+        Ok(Self {
+            n_max: CountDiff {
+                replay: expected.n_max,
+                simulated: simulated.n_max,
+            },
+            n300: CountDiff {
+                replay: expected.n300,
+                simulated: simulated.n300,
+            },
+            n200: CountDiff {
+                replay: expected.n200,
+                simulated: simulated.n200,
+            },
+            n100: CountDiff {
+                replay: expected.n100,
+                simulated: simulated.n100,
+            },
+            n50: CountDiff {
+                replay: expected.n50,
+                simulated: simulated.n50,
+            },
+            n_miss: CountDiff {
+                replay: expected.n_miss,
+                simulated: simulated.n_miss,
+            },
+            max_combo: CountDiff {
+                replay: usize::from(replay.max_combo),
+                simulated: max_combo,
+            },
+            report,
+        })
+    }
+
+    /// Whether all counts agree.
+    pub fn is_valid(&self) -> bool {
+        self.mismatches().next().is_none()
+    }
+
+    /// Names and diffs of all counts that disagree.
+    pub fn mismatches(&self) -> impl Iterator<Item = (&'static str, CountDiff)> {
+        [
+            ("n_max", self.n_max),
+            ("n300", self.n300),
+            ("n200", self.n200),
+            ("n100", self.n100),
+            ("n50", self.n50),
+            ("n_miss", self.n_miss),
+            ("max_combo", self.max_combo),
+        ]
+        .into_iter()
+        .filter(|(_, diff)| !diff.matches())
+    }
+}
+
+/// Run the calculator of the replay's mode and compare its judgements with the replay's header.
+///
+/// The [`Algorithm`] is only used for osu!standard replays, see [`AnyModeCalculator`].
+pub fn validate(
+    algorithm: Algorithm,
+    map: &Beatmap,
+    replay: &Replay,
+) -> Result<Validation, UrError> {
+    validate_with(algorithm, map, replay, &UrOptions::default())
+}
+
+/// Run the calculator of the replay's mode with the given [`UrOptions`] and compare its
+/// judgements with the replay's header.
+///
+/// The [`Algorithm`] is only used for osu!standard replays, see [`AnyModeCalculator`].
+pub fn validate_with(
+    algorithm: Algorithm,
+    map: &Beatmap,
+    replay: &Replay,
+    options: &UrOptions,
+) -> Result<Validation, UrError> {
+    let report = AnyModeCalculator { algorithm }.try_calculate_report_with(map, replay, options)?;
+
+    Validation::new(report, replay)
+}
@@ -0,0 +1,161 @@
+use osu_db::{Mode, Replay};
+use rosu_pp::{parse::Pos2, Beatmap};
+use rosu_ur_calc::{
+    AimOffset, AimReport, AimStatistics, StableCalculator, TaikoCalculator, UrCalculator, UrError,
+};
+
+fn parse_map_replay() -> (Beatmap, Replay) {
+    let map_path = "./test-data/maps/Nico Nico Douga - BARUSA of MIKOSU (DJPop) [TAG4].osu";
+    let replay_path = "./test-data/replays/ekoro_barusa.osr";
+
+    let map = Beatmap::from_path(map_path).expect("failed to parse map");
+    let replay = Replay::from_file(replay_path).expect("failed to parse replay");
+
+    (map, replay)
+}
+
+fn pos(x: f32, y: f32) -> Pos2 {
+    Pos2 { x, y }
+}
+
+fn aim_report(offsets: Vec<AimOffset>) -> AimReport {
+    AimReport {
+        offsets,
+        radius: 10.0,
+    }
+}
+
+#[test]
+fn hits_are_within_circles() {
+    let (map, replay) = parse_map_replay();
+    let report = StableCalculator.calculate_report(&map, &replay);
+    let aim = AimReport::new(&map, &replay, &report).unwrap();
+
+    assert_eq!(aim.offsets.len(), report.hit_errors.len());
+
+    // CS2.5 with Easy, including osu!stable's gamefield rounding allowance
+    assert!((aim.radius - 43.2 * 1.00041).abs() < 1e-4);
+
+    for offset in aim.offsets.iter() {
+        assert!(f64::from(offset.offset().length()) <= aim.radius);
+    }
+
+    let stats = aim.statistics();
+
+    assert!(stats.mean_radial_error > 0.0 && stats.mean_radial_error < aim.radius);
+    assert!(stats.semi_major >= stats.semi_minor);
+
+    let directional = aim.directional_statistics();
+
+    assert!(directional.semi_major >= directional.semi_minor);
+
+    // rotating keeps the distance to the center
+    for offset in aim.offsets.iter() {
+        if let Some(directional) = offset.directional_offset() {
+            assert!((directional.length() - offset.offset().length()).abs() < 1e-3);
+        }
+    }
+}
+
+#[test]
+fn covariance_ellipse() {
+    let offsets = [
+        pos(2.0, 1.0),
+        pos(-2.0, -1.0),
+        pos(1.0, -2.0),
+        pos(-1.0, 2.0),
+    ];
+    let stats = AimStatistics::new(&offsets);
+
+    assert_eq!(stats.mean, pos(0.0, 0.0));
+    assert!((stats.mean_radial_error - 5.0_f64.sqrt()).abs() < 1e-6);
+    assert!((stats.var_x - 2.5).abs() < 1e-9);
+    assert!((stats.var_y - 2.5).abs() < 1e-9);
+    assert!((stats.cov_xy - 0.0).abs() < 1e-9);
+
+    let stretched = [
+        pos(3.0, 3.0),
+        pos(-3.0, -3.0),
+        pos(1.0, -1.0),
+        pos(-1.0, 1.0),
+    ];
+    let stats = AimStatistics::new(&stretched);
+
+    assert!((stats.semi_major - 3.0).abs() < 1e-6);
+    assert!((stats.semi_minor - 1.0).abs() < 1e-6);
+    assert!((stats.angle - std::f64::consts::FRAC_PI_4).abs() < 1e-9);
+}
+
+#[test]
+fn directional_offset() {
+    let offset = AimOffset {
+        index: 1,
+        pos: pos(112.0, 101.0),
+        center: pos(110.0, 100.0),
+        prev_end: Some(pos(10.0, 100.0)),
+    };
+
+    // overshot by 2 and 1 to the right of moving along the x axis
+    assert_eq!(offset.directional_offset(), Some(pos(2.0, 1.0)));
+
+    let upwards = AimOffset {
+        prev_end: Some(pos(110.0, 200.0)),
+        ..offset
+    };
+
+    // short of the center by 1 and 2 to the right of moving up
+    assert_eq!(upwards.directional_offset(), Some(pos(-1.0, 2.0)));
+
+    let stacked = AimOffset {
+        prev_end: Some(offset.center),
+        ..offset
+    };
+
+    assert_eq!(stacked.directional_offset(), None);
+}
+
+#[test]
+fn heatmap() {
+    let centered = AimOffset {
+        index: 1,
+        pos: pos(100.0, 100.0),
+        center: pos(100.0, 100.0),
+        prev_end: Some(pos(0.0, 100.0)),
+    };
+
+    // undershot by a full radius i.e. towards the previous object
+    let undershot = AimOffset {
+        pos: pos(90.0, 100.0),
+        ..centered
+    };
+
+    let first = AimOffset {
+        index: 0,
+        prev_end: None,
+        ..centered
+    };
+
+    let aim = aim_report(vec![first, centered, undershot]);
+    let grid = aim.heatmap(AimReport::LAZER_HEATMAP_SIZE);
+
+    let total: usize = grid.iter().flatten().sum();
+    assert_eq!(total, 2);
+
+    // the previous object is towards the bottom left
+    assert_eq!(grid[10][10], 1);
+    assert_eq!(grid[15][4], 1);
+}
+
+#[test]
+fn taiko_replay_is_rejected() {
+    let (map, mut replay) = parse_map_replay();
+    replay.mode = Mode::Taiko;
+
+    let report = TaikoCalculator.calculate_report(&map, &replay);
+
+    assert!(report.hit_errors.iter().all(|hit| hit.pos.is_none()));
+    assert!(matches!(
+        AimReport::new(&map, &replay, &report),
+        Err(UrError::ModeMismatch { .. })
+    ));
+}
@@ -0,0 +1,209 @@
+use std::path::PathBuf;
+
+use osu_db::Replay;
+use rosu_pp::Beatmap;
+use rosu_ur_calc::{
+    calculate_batch, find_replays, BatchError, BatchOptions, BeatmapIndex, HitErrorReport,
+    StableCalculator, UrCalculator, UrError, UrOptions,
+};
+
+const REPLAYS: [&str; 3] = [
+    "respektive_high_powered",
+    "respektive_haitai",
+    "mismagius_usatei",
+];
+
+fn replay_paths() -> Vec<PathBuf> {
+    REPLAYS
+        .iter()
+        .map(|name| PathBuf::from(format!("./test-data/replays/{name}.osr")))
+        .collect()
+}
+
+#[test]
+fn songs_dir_contains_every_replay_map() {
+    let index = BeatmapIndex::from_songs_dir("./test-data/maps").unwrap();
+    let replay_paths = find_replays("./test-data/replays").unwrap();
+
+    assert_eq!(index.len(), 14);
+    assert!(replay_paths.len() >= REPLAYS.len());
+
+    for path in replay_paths {
+        let replay = Replay::from_file(&path).unwrap();
+        let hash = replay.beatmap_hash.unwrap();
+
+        assert!(index.get(&hash).is_some(), "{path:?}");
+        assert_eq!(index.get(&hash), index.get(&hash.to_ascii_uppercase()));
+    }
+}
+
+#[test]
+fn matches_single_calculations() {
+    let index = BeatmapIndex::from_songs_dir("./test-data/maps").unwrap();
+    let replay_paths = replay_paths();
+
+    let options = BatchOptions {
+        threads: Some(2),
+        ..Default::default()
+    };
+
+    let entries = calculate_batch(&StableCalculator, &replay_paths, &index, &options);
+
+    assert_eq!(entries.len(), replay_paths.len());
+
+    for (entry, path) in entries.iter().zip(&replay_paths) {
+        assert_eq!(&entry.replay_path, path);
+
+        let replay = Replay::from_file(path).unwrap();
+        let map = Beatmap::from_path(entry.map_path.as_ref().unwrap()).unwrap();
+        let expected = StableCalculator.calculate_report(&map, &replay);
+        let report = entry.result.as_ref().unwrap();
+
+        assert_eq!(report.unstable_rate, expected.unstable_rate);
+        assert_eq!(report.judgements, expected.judgements);
+
+        let header = entry.replay.as_ref().unwrap();
+        assert_eq!(header.beatmap_hash, replay.beatmap_hash);
+        assert!(header.replay_data.is_none());
+    }
+}
+
+#[test]
+fn failures_are_recorded() {
+    let mut replay_paths = replay_paths();
+    replay_paths.insert(1, PathBuf::from("./test-data/replays/missing.osr"));
+
+    let entries = calculate_batch(
+        &StableCalculator,
+        &replay_paths,
+        &BeatmapIndex::new(),
+        &BatchOptions::default(),
+    );
+
+    assert_eq!(entries.len(), replay_paths.len());
+    assert!(matches!(entries[1].result, Err(BatchError::ReadReplay(_))));
+    assert!(entries[1].replay.is_none());
+
+    for entry in [&entries[0], &entries[2], &entries[3]] {
+        let hash = entry.replay.as_ref().unwrap().beatmap_hash.clone().unwrap();
+
+        assert!(matches!(&entry.result, Err(BatchError::BeatmapNotFound(h)) if *h == hash));
+        assert!(entry.map_path.is_none());
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn unreadable_beatmaps_are_skipped() {
+    let songs_dir = std::env::temp_dir().join("rosu-ur-calc-unreadable-beatmaps");
+    let _ = std::fs::remove_dir_all(&songs_dir);
+    std::fs::create_dir_all(&songs_dir).unwrap();
+
+    let map_file = "IOSYS - Usatei (Card N'FoRcE) [RUN!!].osu";
+    std::fs::copy(
+        format!("./test-data/maps/{map_file}"),
+        songs_dir.join(map_file),
+    )
+    .unwrap();
+    std::os::unix::fs::symlink(songs_dir.join("missing.osu"), songs_dir.join("broken.osu"))
+        .unwrap();
+
+    let index = BeatmapIndex::from_songs_dir(&songs_dir);
+    let _ = std::fs::remove_dir_all(&songs_dir);
+
+    assert_eq!(index.unwrap().len(), 1);
+}
+
+#[test]
+fn index_file_round_trip() {
+    let index = BeatmapIndex::from_songs_dir("./test-data/maps").unwrap();
+
+    let path = std::env::temp_dir().join("rosu-ur-calc-batch-index.txt");
+    let mut bytes = b"# generated by a test\n\n".to_vec();
+    index.write_index(&mut bytes).unwrap();
+    std::fs::write(&path, bytes).unwrap();
+
+    let read = BeatmapIndex::from_index_file(&path);
+    let _ = std::fs::remove_file(&path);
+
+    assert_eq!(read.unwrap(), index);
+}
+
+#[test]
+fn modified_beatmaps_are_rejected() {
+    let replay_paths = replay_paths();
+    let replays: Vec<_> = replay_paths
+        .iter()
+        .map(|path| Replay::from_file(path).unwrap())
+        .collect();
+
+    // every hash points at the map of the first replay
+    let mut index = BeatmapIndex::new();
+    let map_path = BeatmapIndex::from_songs_dir("./test-data/maps")
+        .unwrap()
+        .find(&replays[0])
+        .unwrap()
+        .to_owned();
+
+    for replay in &replays {
+        index.insert(replay.beatmap_hash.clone().unwrap(), map_path.clone());
+    }
+
+    let entries = calculate_batch(
+        &StableCalculator,
+        &replay_paths,
+        &index,
+        &BatchOptions::default(),
+    );
+
+    assert!(entries[0].result.is_ok());
+
+    for entry in &entries[1..] {
+        assert!(matches!(
+            entry.result,
+            Err(BatchError::Calculate(UrError::BeatmapMismatch { .. }))
+        ));
+    }
+
+    let options = BatchOptions {
+        allow_modified_beatmaps: true,
+        ..Default::default()
+    };
+
+    let entries = calculate_batch(&StableCalculator, &replay_paths[..2], &index, &options);
+
+    assert!(entries.iter().all(|entry| entry.result.is_ok()));
+}
+
+struct PanickingCalculator;
+
+impl UrCalculator for PanickingCalculator {
+    fn try_calculate_report_with(
+        &self,
+        _: &Beatmap,
+        _: &Replay,
+        _: &UrOptions,
+    ) -> Result<HitErrorReport, UrError> {
+        panic!("calculator bug")
+    }
+}
+
+#[test]
+fn panics_are_recorded() {
+    let index = BeatmapIndex::from_songs_dir("./test-data/maps").unwrap();
+    let replay_paths = replay_paths();
+
+    let options = BatchOptions {
+        threads: Some(2),
+        ..Default::default()
+    };
+
+    let entries = calculate_batch(&PanickingCalculator, &replay_paths, &index, &options);
+
+    assert_eq!(entries.len(), replay_paths.len());
+
+    for (entry, path) in entries.iter().zip(replay_paths.iter()) {
+        assert_eq!(&entry.replay_path, path);
+        assert!(matches!(&entry.result, Err(BatchError::Panic(msg)) if msg == "calculator bug"));
+    }
+}
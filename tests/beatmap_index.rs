@@ -0,0 +1,193 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use osu_db::{Listing, Replay};
+use rosu_pp::Beatmap;
+use rosu_ur_calc::{BeatmapIndex, StableCalculator, UrCalculator};
+
+const MAP_FILE: &str = "sphere - HIGH POWERED (TV Size) (Azunyan-) [POWER OVERLOAD EXPERT].osu";
+const REPLAY: &str = "./test-data/replays/respektive_high_powered.osr";
+
+struct ListedBeatmap<'a> {
+    hash: Option<&'a str>,
+    folder_name: Option<&'a str>,
+    file_name: Option<&'a str>,
+}
+
+/// Encode an `osu!.db` in the format of version 20191106 and later.
+fn osu_db(player_name: &str, beatmaps: &[ListedBeatmap<'_>]) -> Vec<u8> {
+    fn string(bytes: &mut Vec<u8>, s: Option<&str>) {
+        match s {
+            Some(s) => {
+                bytes.push(0x0b);
+                bytes.push(s.len() as u8);
+                bytes.extend_from_slice(s.as_bytes());
+            }
+            None => bytes.push(0x00),
+        }
+    }
+
+    let int = |bytes: &mut Vec<u8>, n: u32| bytes.extend_from_slice(&n.to_le_bytes());
+
+    let mut bytes = Vec::new();
+    int(&mut bytes, 20191106);
+    int(&mut bytes, beatmaps.len() as u32);
+    bytes.push(1); // account unlocked
+    bytes.extend_from_slice(&0_u64.to_le_bytes());
+    string(&mut bytes, Some(player_name));
+    int(&mut bytes, beatmaps.len() as u32);
+
+    for beatmap in beatmaps {
+        // artist, title, creator, difficulty, and audio
+        for _ in 0..7 {
+            string(&mut bytes, None);
+        }
+
+        string(&mut bytes, beatmap.hash);
+        string(&mut bytes, beatmap.file_name);
+        bytes.push(4); // ranked
+        bytes.extend_from_slice(&[0; 6]); // object counts
+        bytes.extend_from_slice(&0_u64.to_le_bytes());
+
+        // AR, CS, HP, and OD
+        for _ in 0..4 {
+            bytes.extend_from_slice(&5.0_f32.to_le_bytes());
+        }
+
+        bytes.extend_from_slice(&1.4_f64.to_le_bytes());
+
+        // star ratings of each mode, drain, total, and preview time, and timing points
+        for _ in 0..8 {
+            int(&mut bytes, 0);
+        }
+
+        // beatmap, beatmapset, and thread id
+        for _ in 0..3 {
+            int(&mut bytes, 0);
+        }
+
+        bytes.extend_from_slice(&[9; 4]); // unplayed grades
+        bytes.extend_from_slice(&0_u16.to_le_bytes());
+        bytes.extend_from_slice(&0.7_f32.to_le_bytes());
+        bytes.push(0); // osu!standard
+        string(&mut bytes, None);
+        string(&mut bytes, None);
+        bytes.extend_from_slice(&0_u16.to_le_bytes());
+        string(&mut bytes, None);
+        bytes.push(1); // unplayed
+        bytes.extend_from_slice(&0_u64.to_le_bytes());
+        bytes.push(0); // osz2
+        string(&mut bytes, beatmap.folder_name);
+        bytes.extend_from_slice(&0_u64.to_le_bytes());
+        bytes.extend_from_slice(&[0; 5]);
+        int(&mut bytes, 0);
+        bytes.push(0);
+    }
+
+    int(&mut bytes, 0);
+
+    bytes
+}
+
+/// A fake osu! directory whose `osu!.db` lists the test map in the given songs directory.
+fn osu_dir(name: &str, songs_dir: &str) -> PathBuf {
+    let osu_dir = std::env::temp_dir().join(format!("rosu-ur-calc-{name}"));
+    let folder = osu_dir.join(songs_dir).join("1 sphere - HIGH POWERED");
+    fs::create_dir_all(&folder).unwrap();
+    fs::copy(
+        format!("./test-data/maps/{MAP_FILE}"),
+        folder.join(MAP_FILE),
+    )
+    .unwrap();
+
+    let hash = Replay::from_file(REPLAY).unwrap().beatmap_hash.unwrap();
+
+    let beatmaps = [
+        ListedBeatmap {
+            hash: Some(&hash),
+            folder_name: Some("1 sphere - HIGH POWERED"),
+            file_name: Some(MAP_FILE),
+        },
+        ListedBeatmap {
+            hash: None,
+            folder_name: Some("2 unknown"),
+            file_name: Some("unknown.osu"),
+        },
+        ListedBeatmap {
+            hash: Some("0123456789abcdef0123456789abcdef"),
+            folder_name: None,
+            file_name: Some("unknown.osu"),
+        },
+    ];
+
+    fs::write(osu_dir.join("osu!.db"), osu_db("player", &beatmaps)).unwrap();
+
+    osu_dir
+}
+
+fn assert_finds_replay_map(osu_dir: &Path, songs_dir: &Path) {
+    let index = BeatmapIndex::from_osu_dir(osu_dir).unwrap();
+    let replay = Replay::from_file(REPLAY).unwrap();
+
+    assert_eq!(index.len(), 1);
+
+    let map_path = index.find(&replay).unwrap();
+    assert_eq!(map_path.parent().unwrap().parent().unwrap(), songs_dir);
+
+    let map = Beatmap::from_path(map_path).unwrap();
+    assert!(StableCalculator.try_calculate_ur(&map, &replay).is_ok());
+}
+
+#[test]
+fn listing_is_parsed() {
+    let osu_dir = osu_dir("listing", "Songs");
+    let listing = Listing::from_file(osu_dir.join("osu!.db"));
+    let _ = fs::remove_dir_all(&osu_dir);
+    let listing = listing.unwrap();
+
+    assert_eq!(listing.player_name.as_deref(), Some("player"));
+    assert_eq!(listing.beatmaps.len(), 3);
+    assert_eq!(listing.beatmaps[0].file_name.as_deref(), Some(MAP_FILE));
+
+    let index = BeatmapIndex::from_listing(&listing, "/osu/Songs");
+    let expected = Path::new("/osu/Songs/1 sphere - HIGH POWERED").join(MAP_FILE);
+
+    assert_eq!(index.len(), 1);
+    assert_eq!(
+        index.get(listing.beatmaps[0].hash.as_deref().unwrap()),
+        Some(expected.as_path())
+    );
+}
+
+#[test]
+fn songs_folder() {
+    let osu_dir = osu_dir("songs-folder", "Songs");
+    assert_finds_replay_map(&osu_dir, &osu_dir.join("Songs"));
+    let _ = fs::remove_dir_all(&osu_dir);
+}
+
+#[test]
+fn configured_beatmap_directory() {
+    let osu_dir = osu_dir("beatmap-directory", "Beatmaps");
+
+    // named after the operating system's account rather than the player, and the global
+    // config is not the user's
+    fs::write(
+        osu_dir.join("osu!.WindowsUser.cfg"),
+        "VolumeUniversal = 100\nBeatmapDirectory = Beatmaps\n",
+    )
+    .unwrap();
+    fs::write(osu_dir.join("osu!.cfg"), "BeatmapDirectory = Songs\n").unwrap();
+
+    assert_finds_replay_map(&osu_dir, &osu_dir.join("Beatmaps"));
+    let _ = fs::remove_dir_all(&osu_dir);
+}
+
+#[test]
+fn missing_osu_db() {
+    let err = BeatmapIndex::from_osu_dir("./test-data").unwrap_err();
+
+    assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+}
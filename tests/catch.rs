@@ -0,0 +1,189 @@
+use osu_db::{replay::Action, Mod, ModSet, Mode, Replay};
+use rosu_pp::{Beatmap, BeatmapExt, CatchStars};
+use rosu_ur_calc::{
+    CatchCalculator, CatchObjectKind, HitCounts, TimeScale, UrCalculator, UrError, UrOptions,
+    Validation,
+};
+
+fn parse_map_replay() -> (Beatmap, Replay) {
+    let map_path = "./test-data/maps/IOSYS - Usatei (Card N'FoRcE) [RUN!!].osu";
+    let replay_path = "./test-data/replays/mismagius_usatei.osr";
+
+    let map = Beatmap::from_path(map_path).expect("failed to parse map");
+    let mut replay = Replay::from_file(replay_path).expect("failed to parse replay");
+
+    replay.mode = Mode::CatchTheBeat;
+    replay.mods = ModSet::empty();
+
+    (map, replay)
+}
+
+/// Move the catcher onto every object of the converted map, `offset` pixels to its left.
+fn play(map: &Beatmap, replay: &mut Replay, offset: f32) {
+    let mut prev_time = 0;
+
+    let actions = map
+        .catch_hitobjects(0)
+        .iter()
+        .map(|h| {
+            let time = h.time.round() as i64;
+
+            let action = Action {
+                delta: time - prev_time,
+                x: h.pos - offset,
+                y: 0.0,
+                z: 1.0,
+            };
+
+            prev_time = time;
+
+            action
+        })
+        .collect();
+
+    replay.replay_data = Some(actions);
+}
+
+#[test]
+fn perfect_play() {
+    let (map, mut replay) = parse_map_replay();
+    play(&map, &mut replay, 0.0);
+
+    let report = CatchCalculator.calculate_report(&map, &replay);
+    let attrs = CatchStars::new(&map).calculate();
+
+    assert_eq!(report.unstable_rate, 0.0);
+    assert!(report.hit_errors.is_empty());
+    assert_eq!(report.n_judged, attrs.n_fruits + attrs.n_droplets);
+    assert_eq!(report.n_missed, 0);
+    assert_eq!(
+        report.hit_counts(),
+        Some(HitCounts {
+            n300: attrs.n_fruits,
+            n100: attrs.n_droplets,
+            ..Default::default()
+        })
+    );
+
+    let n_droplets = report
+        .catches
+        .iter()
+        .filter(|catch| catch.kind == CatchObjectKind::Droplet)
+        .count();
+
+    assert_eq!(n_droplets, attrs.n_droplets);
+    assert!(report.catches.iter().all(|catch| catch.dashing));
+}
+
+#[test]
+fn offsets() {
+    let (map, mut replay) = parse_map_replay();
+    play(&map, &mut replay, 20.0);
+
+    let report = CatchCalculator.calculate_report(&map, &replay);
+
+    assert_eq!(report.n_missed, 0);
+
+    // frames are rounded to full ms so the catcher is slightly off between them
+    let mut offsets: Vec<_> = report.catches.iter().map(|catch| catch.offset).collect();
+    offsets.sort_by(f64::total_cmp);
+
+    assert!((offsets[offsets.len() / 2] - 20.0).abs() < 0.5);
+}
+
+#[test]
+fn missing_everything() {
+    let (map, mut replay) = parse_map_replay();
+    play(&map, &mut replay, 100.0);
+
+    assert_eq!(
+        CatchCalculator
+            .try_calculate_report(&map, &replay)
+            .unwrap_err(),
+        UrError::NoHitsDetected
+    );
+}
+
+#[test]
+fn bananas() {
+    let map_path =
+        "./test-data/maps/sphere - HIGH POWERED (TV Size) (Azunyan-) [POWER OVERLOAD EXPERT].osu";
+    let map = Beatmap::from_path(map_path).expect("failed to parse map");
+    let (_, mut replay) = parse_map_replay();
+    play(&map, &mut replay, 0.0);
+
+    let report = CatchCalculator.calculate_report(&map, &replay);
+    let attrs = CatchStars::new(&map).calculate();
+
+    // 33 bananas of which only those that happen to fall onto the catcher are caught
+    assert_eq!(report.n_bananas, 9);
+    assert_eq!(report.n_skipped, attrs.n_tiny_droplets + 33);
+    assert_eq!(report.n_missed, 0);
+}
+
+#[test]
+fn tiny_droplets_are_skipped() {
+    let (map, mut replay) = parse_map_replay();
+    play(&map, &mut replay, 0.0);
+
+    let report = CatchCalculator.calculate_report(&map, &replay);
+    let attrs = CatchStars::new(&map).calculate();
+
+    assert_eq!(report.n_bananas, 0);
+    assert_eq!(report.n_skipped, attrs.n_tiny_droplets);
+}
+
+#[test]
+fn validation_ignores_tiny_droplets() {
+    let (map, mut replay) = parse_map_replay();
+    play(&map, &mut replay, 0.0);
+
+    let attrs = CatchStars::new(&map).calculate();
+
+    replay.count_300 = attrs.n_fruits as u16;
+    replay.count_100 = attrs.n_droplets as u16;
+    replay.count_50 = attrs.n_tiny_droplets as u16;
+    replay.count_katsu = 0;
+    replay.count_geki = 0;
+    replay.count_miss = 0;
+    replay.max_combo = (attrs.n_fruits + attrs.n_droplets) as u16;
+
+    let report = CatchCalculator.calculate_report(&map, &replay);
+    let validation = Validation::new(report, &replay).unwrap();
+
+    assert_eq!(validation.n50.replay, 0);
+    assert!(
+        validation.is_valid(),
+        "{:?}",
+        validation.mismatches().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn real_time_keeps_empty_statistics() {
+    let (map, mut replay) = parse_map_replay();
+    play(&map, &mut replay, 0.0);
+    replay.mods = ModSet::empty().with(Mod::DoubleTime);
+
+    let options = UrOptions {
+        time_scale: TimeScale::RealTime,
+        ..Default::default()
+    };
+
+    let report = CatchCalculator.calculate_report_with(&map, &replay, &options);
+
+    assert_eq!(report.clock_rate, 1.5);
+    assert_eq!(report.unstable_rate, 0.0);
+    assert_eq!(report.mean, 0.0);
+}
+
+#[test]
+fn osu_replay_is_rejected() {
+    let (map, mut replay) = parse_map_replay();
+    replay.mode = Mode::Standard;
+
+    assert!(matches!(
+        CatchCalculator.try_calculate_ur(&map, &replay),
+        Err(UrError::ModeMismatch { .. })
+    ));
+}
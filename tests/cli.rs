@@ -0,0 +1,196 @@
+#![cfg(feature = "cli")]
+
+use std::process::{Command, Output};
+
+use osu_db::{Mode, Replay};
+use serde_json::Value;
+
+const MAP: &str =
+    "./test-data/maps/sphere - HIGH POWERED (TV Size) (Azunyan-) [POWER OVERLOAD EXPERT].osu";
+const REPLAY: &str = "./test-data/replays/respektive_high_powered.osr";
+
+fn run(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_rosu-ur-calc"))
+        .args(args)
+        .output()
+        .expect("failed to run the cli")
+}
+
+fn stdout_json(output: &Output) -> Value {
+    assert!(output.status.success(), "{output:?}");
+
+    serde_json::from_slice(&output.stdout).expect("stdout is not json")
+}
+
+#[test]
+fn ur_as_json() {
+    let output = run(&[
+        "ur",
+        MAP,
+        REPLAY,
+        "--algorithm",
+        "stable",
+        "--format",
+        "json",
+    ]);
+    let json = stdout_json(&output);
+
+    assert_eq!(json["algorithm"], "stable");
+
+    let unstable_rate = json["unstable_rate"].as_f64().unwrap();
+    assert!((unstable_rate - 90.8).abs() < 0.01, "{unstable_rate}");
+}
+
+#[test]
+fn report_with_mod_override() {
+    let output = run(&["report", MAP, REPLAY, "-m", "HDNC", "-f", "json"]);
+    let json = stdout_json(&output);
+
+    assert_eq!(json["mods"], "HDNC");
+    assert_eq!(json["clock_rate"], 1.5);
+    assert_eq!(json["time_scale"], "real");
+    assert_eq!(
+        json["k1_hits"].as_u64().unwrap() + json["k2_hits"].as_u64().unwrap(),
+        json["n_hits"].as_u64().unwrap()
+    );
+
+    let output = run(&[
+        "report",
+        MAP,
+        REPLAY,
+        "-m",
+        "HDNC",
+        "--map-time",
+        "-f",
+        "json",
+    ]);
+    let map_time = stdout_json(&output);
+
+    assert_eq!(map_time["time_scale"], "map");
+
+    let real_time_ur = json["unstable_rate"].as_f64().unwrap();
+    let map_time_ur = map_time["unstable_rate"].as_f64().unwrap();
+    assert!((map_time_ur / 1.5 - real_time_ur).abs() < 1e-9);
+}
+
+#[test]
+fn compare_as_csv() {
+    let output = run(&["compare", MAP, REPLAY, "--format", "csv"]);
+    assert!(output.status.success(), "{output:?}");
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let mut lines = stdout.lines();
+
+    let header: Vec<_> = lines.next().unwrap().split(',').collect();
+    assert_eq!(header[0], "algorithm");
+    assert_eq!(header.last(), Some(&"error"));
+
+    let rows: Vec<Vec<_>> = lines.map(|line| line.split(',').collect()).collect();
+    assert_eq!(rows.len(), 7);
+
+    for row in rows {
+        assert_eq!(row.len(), header.len());
+        assert_eq!(row.last(), Some(&""));
+    }
+}
+
+#[test]
+fn exit_codes() {
+    let unreadable = run(&["ur", "./test-data/maps/missing.osu", REPLAY]);
+    assert_eq!(unreadable.status.code(), Some(1));
+    assert!(unreadable.stdout.is_empty());
+
+    let invalid_mods = run(&["ur", MAP, REPLAY, "--mods", "HDX"]);
+    assert_eq!(invalid_mods.status.code(), Some(2));
+
+    let unknown_algorithm = run(&["ur", MAP, REPLAY, "--algorithm", "osu"]);
+    assert_eq!(unknown_algorithm.status.code(), Some(2));
+
+    let no_subcommand = run(&[]);
+    assert_eq!(no_subcommand.status.code(), Some(2));
+}
+
+#[test]
+fn modified_map_is_rejected() {
+    let map = "./test-data/maps/Gitaroo Man - Soft Machine (Ash) [Master Mode].osu";
+
+    let output = run(&["ur", map, REPLAY]);
+    assert_eq!(output.status.code(), Some(3), "{output:?}");
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("does not match"), "{stderr}");
+
+    let output = run(&["ur", map, REPLAY, "--allow-modified"]);
+    assert!(output.status.success(), "{output:?}");
+}
+
+#[test]
+fn compare_fails_if_no_algorithm_succeeds() {
+    // every algorithm is osu!standard only
+    let mut replay = Replay::from_file(REPLAY).unwrap();
+    replay.mode = Mode::Taiko;
+
+    let path = std::env::temp_dir().join("rosu-ur-calc-cli-taiko.osr");
+    replay.save(&path, None).unwrap();
+
+    let output = run(&["compare", MAP, path.to_str().unwrap(), "-f", "json"]);
+    let _ = std::fs::remove_file(&path);
+
+    assert_eq!(output.status.code(), Some(3), "{output:?}");
+
+    let json: Value = serde_json::from_slice(&output.stdout).unwrap();
+    let rows = json.as_array().unwrap();
+
+    assert_eq!(rows.len(), 7);
+    assert!(rows.iter().all(|row| row["error"].is_string()));
+}
+
+#[test]
+fn batch_with_index_file() {
+    let dir = std::env::temp_dir().join("rosu-ur-calc-cli-batch");
+    let replay_dir = dir.join("replays");
+    std::fs::create_dir_all(&replay_dir).unwrap();
+
+    std::fs::copy(REPLAY, replay_dir.join("a.osr")).unwrap();
+    std::fs::write(replay_dir.join("b.osr"), b"not a replay").unwrap();
+
+    let index_path = dir.join("index.txt");
+    let map = std::fs::canonicalize(MAP).unwrap();
+    let hash = Replay::from_file(REPLAY).unwrap().beatmap_hash.unwrap();
+    std::fs::write(&index_path, format!("{hash} {}\n", map.display())).unwrap();
+
+    let output = run(&[
+        "batch",
+        replay_dir.to_str().unwrap(),
+        "--index",
+        index_path.to_str().unwrap(),
+        "-f",
+        "json",
+    ]);
+
+    let _ = std::fs::remove_dir_all(&dir);
+    let json = stdout_json(&output);
+    let rows = json.as_array().unwrap();
+
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0]["beatmap_hash"], hash.as_str());
+    assert!(rows[0]["unstable_rate"].is_f64());
+    assert!(rows[0]["error"].is_null());
+    assert!(rows[1]["unstable_rate"].is_null());
+    assert!(rows[1]["error"].is_string());
+}
+
+#[test]
+fn osu_dir_takes_only_the_replay() {
+    let both_paths = run(&["ur", "--osu", "./test-data", MAP, REPLAY]);
+    assert_eq!(both_paths.status.code(), Some(2));
+
+    let missing_osu_db = run(&["ur", "--osu", "./test-data", REPLAY]);
+    assert_eq!(missing_osu_db.status.code(), Some(1));
+
+    let stderr = String::from_utf8(missing_osu_db.stderr).unwrap();
+    assert!(stderr.contains("osu!.db"), "{stderr}");
+
+    let no_beatmap = run(&["ur", REPLAY]);
+    assert_eq!(no_beatmap.status.code(), Some(2));
+}
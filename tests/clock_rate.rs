@@ -0,0 +1,105 @@
+use osu_db::Replay;
+use rosu_pp::Beatmap;
+use rosu_ur_calc::{calculate_ur, Algorithm, TimeScale, UrCalculator, UrOptions};
+
+fn parse_map_replay(map_file: &str, replay_file: &str) -> (Beatmap, Replay) {
+    let map_path = format!("./test-data/maps/{map_file}.osu");
+    let replay_path = format!("./test-data/replays/{replay_file}.osr");
+
+    let map = Beatmap::from_path(map_path).expect("failed to parse map");
+    let replay = Replay::from_file(replay_path).expect("failed to parse replay");
+
+    (map, replay)
+}
+
+fn unstable_rate(
+    map: &Beatmap,
+    replay: &Replay,
+    clock_rate: Option<f64>,
+    time_scale: TimeScale,
+) -> f64 {
+    let options = UrOptions {
+        clock_rate,
+        time_scale,
+        ..Default::default()
+    };
+
+    Algorithm::default()
+        .calculate_report_with(map, replay, &options)
+        .unstable_rate
+}
+
+fn real_time_ur(map: &Beatmap, replay: &Replay, clock_rate: Option<f64>) -> f64 {
+    unstable_rate(map, replay, clock_rate, TimeScale::RealTime)
+}
+
+fn map_time_ur(map: &Beatmap, replay: &Replay) -> f64 {
+    unstable_rate(map, replay, None, TimeScale::MapTime)
+}
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() <= 1e-9,
+        "expected {expected}, got {actual}",
+    );
+}
+
+#[test]
+fn double_time() {
+    let (map, replay) = parse_map_replay(
+        "Euchaeta - Who's World (P_O) [Who Does This World Belong To]",
+        "respektive_whos_world",
+    );
+
+    let map_time = map_time_ur(&map, &replay);
+
+    assert_close(real_time_ur(&map, &replay, None), map_time / 1.5);
+    assert_close(calculate_ur(&map, &replay), map_time / 1.5);
+}
+
+#[test]
+fn half_time() {
+    let (map, replay) = parse_map_replay(
+        "Nico Nico Douga - BARUSA of MIKOSU (DJPop) [TAG4]",
+        "ekoro_barusa",
+    );
+
+    let map_time = map_time_ur(&map, &replay);
+
+    assert_close(real_time_ur(&map, &replay, None), map_time / 0.75);
+}
+
+#[test]
+fn nomod_is_unaffected() {
+    let (map, replay) = parse_map_replay(
+        "Gitaroo Man - Soft Machine (Ash) [Master Mode]",
+        "wolf_gitaroo",
+    );
+
+    let map_time = map_time_ur(&map, &replay);
+
+    assert_close(real_time_ur(&map, &replay, None), map_time);
+}
+
+#[test]
+fn custom_clock_rate() {
+    let (map, replay) = parse_map_replay(
+        "Gitaroo Man - Soft Machine (Ash) [Master Mode]",
+        "wolf_gitaroo",
+    );
+
+    let map_time = map_time_ur(&map, &replay);
+    let report = Algorithm::Stable.calculate_report_with(
+        &map,
+        &replay,
+        &UrOptions {
+            clock_rate: Some(1.2),
+            time_scale: TimeScale::RealTime,
+            ..Default::default()
+        },
+    );
+
+    assert_close(real_time_ur(&map, &replay, Some(1.2)), map_time / 1.2);
+    assert_close(report.clock_rate, 1.2);
+    assert_eq!(report.time_scale, TimeScale::RealTime);
+}
@@ -60,7 +60,7 @@ fn respektive_whos_world() {
     compare_ur(
         "Euchaeta - Who's World (P_O) [Who Does This World Belong To]",
         "respektive_whos_world",
-        96.14,
+        64.09,
     )
 }
 
@@ -96,7 +96,7 @@ fn ekoro_barusa() {
     compare_ur(
         "Nico Nico Douga - BARUSA of MIKOSU (DJPop) [TAG4]",
         "ekoro_barusa",
-        115.33,
+        153.77,
     )
 }
 
@@ -114,7 +114,7 @@ fn mismagius_usatei() {
     compare_ur(
         "IOSYS - Usatei (Card N'FoRcE) [RUN!!]",
         "mismagius_usatei",
-        218.45,
+        145.63,
     )
 }
 
@@ -0,0 +1,95 @@
+use osu_db::Replay;
+use rosu_pp::Beatmap;
+use rosu_ur_calc::{
+    ConsistencyReport, ErrorStatistics, SectionSplit, StableCalculator, UrCalculator, Window,
+};
+
+fn parse_map_replay() -> (Beatmap, Replay) {
+    let map_path = "./test-data/maps/Within Temptation - The Unforgiving (Armin) [Marathon].osu";
+    let replay_path = "./test-data/replays/respektive_unforgiving.osr";
+
+    let map = Beatmap::from_path(map_path).expect("failed to parse map");
+    let replay = Replay::from_file(replay_path).expect("failed to parse replay");
+
+    (map, replay)
+}
+
+#[test]
+fn rolling_objects() {
+    let (map, replay) = parse_map_replay();
+    let report = StableCalculator.calculate_report(&map, &replay);
+    let consistency = ConsistencyReport::new(&map, &replay, &report);
+
+    let segments = consistency.rolling(Window::Objects(100));
+
+    assert_eq!(segments.len(), report.hit_errors.len() - 99);
+    assert!(segments.iter().all(|segment| segment.n_hits == 100));
+    assert!(segments
+        .windows(2)
+        .all(|pair| pair[0].start_time <= pair[1].start_time));
+
+    let errors: Vec<_> = report.hit_errors[..100].iter().map(|h| h.error).collect();
+    let expected = ErrorStatistics::new(&errors).unstable_rate;
+
+    assert!((segments[0].unstable_rate() - expected).abs() < 1e-9);
+
+    assert!(consistency.rolling(Window::Objects(0)).is_empty());
+}
+
+#[test]
+fn rolling_duration() {
+    let (map, replay) = parse_map_replay();
+    let report = StableCalculator.calculate_report(&map, &replay);
+    let consistency = ConsistencyReport::new(&map, &replay, &report);
+
+    let segments = consistency.rolling(Window::Duration(10_000.0));
+
+    assert_eq!(segments.len(), report.hit_errors.len());
+    assert_eq!(segments[0].n_hits, 1);
+
+    for segment in segments.iter() {
+        assert!(segment.end_time - segment.start_time < 10_000.0);
+        assert!(segment.n_hits >= 1);
+    }
+}
+
+#[test]
+fn sections_at_breaks() {
+    let (map, replay) = parse_map_replay();
+    let report = StableCalculator.calculate_report(&map, &replay);
+    let consistency = ConsistencyReport::new(&map, &replay, &report);
+
+    let sections = consistency.sections(SectionSplit::Breaks);
+
+    assert_eq!(sections.len(), map.breaks.len() + 1);
+
+    let n_hits: usize = sections.iter().map(|section| section.n_hits).sum();
+    assert_eq!(n_hits, report.hit_errors.len());
+
+    for (section, b) in sections.iter().zip(map.breaks.iter()) {
+        assert_eq!(section.end_time, b.start_time);
+    }
+
+    for (section, b) in sections[1..].iter().zip(map.breaks.iter()) {
+        assert_eq!(section.start_time, b.end_time);
+    }
+}
+
+#[test]
+fn sections_at_timing_points() {
+    let (map, replay) = parse_map_replay();
+    let report = StableCalculator.calculate_report(&map, &replay);
+    let consistency = ConsistencyReport::new(&map, &replay, &report);
+
+    let sections = consistency.sections(SectionSplit::TimingPoints);
+
+    assert!(sections.len() > 1);
+    assert!(sections.len() <= map.timing_points.len());
+
+    let n_hits: usize = sections.iter().map(|section| section.n_hits).sum();
+    assert_eq!(n_hits, report.hit_errors.len());
+
+    assert!(sections
+        .windows(2)
+        .all(|pair| pair[0].end_time <= pair[1].start_time));
+}
@@ -0,0 +1,74 @@
+use rosu_ur_calc::ErrorStatistics;
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() <= 1e-9,
+        "expected {expected}, got {actual}",
+    );
+}
+
+#[test]
+fn integer_and_float_errors_match() {
+    let ints = [-10, -4, 0, 2, 12];
+    let floats = [-10.0, -4.0, 0.0, 2.0, 12.0];
+
+    let from_ints = ErrorStatistics::new(&ints);
+    let from_floats = ErrorStatistics::new(&floats);
+
+    assert_close(from_ints.unstable_rate, from_floats.unstable_rate);
+    assert_close(from_ints.avg, 0.0);
+    assert_close(from_ints.minus_avg, -7.0);
+    assert_close(from_ints.plus_avg, 14.0 / 3.0);
+    assert_close(from_ints.min, -10.0);
+    assert_close(from_ints.max, 12.0);
+    assert_close(from_ints.median, 0.0);
+}
+
+#[test]
+fn late_hits_only() {
+    let stats = ErrorStatistics::new(&[3, 5, 10]);
+
+    assert_close(stats.minus_avg, 0.0);
+    assert_close(stats.minus_max, 3.0);
+    assert_close(stats.plus_max, 10.0);
+}
+
+#[test]
+fn percentiles_interpolate() {
+    let stats = ErrorStatistics::new(&[1, 2, 3, 4]);
+
+    assert_close(stats.percentile(0.0), 1.0);
+    assert_close(stats.percentile(100.0), 4.0);
+    assert_close(stats.median, 2.5);
+    assert_close(stats.percentile(25.0), 1.75);
+}
+
+#[test]
+fn moments() {
+    let stats = ErrorStatistics::new(&[2, 4, 4, 4, 5, 5, 7, 9]);
+
+    assert_close(stats.unstable_rate, 20.0);
+    assert_close(stats.standard_error, 2.0 / 8.0_f64.sqrt());
+    assert!(stats.skewness > 0.0);
+}
+
+#[test]
+fn trimmed_unstable_rate_ignores_outliers() {
+    let mut hit_errors = vec![-1, 1, -1, 1, -1, 1, -1, 1, -1];
+    hit_errors.push(100);
+
+    let stats = ErrorStatistics::new(&hit_errors);
+
+    assert!(stats.unstable_rate > 100.0);
+    assert!(stats.trimmed_unstable_rate(10.0) < 11.0);
+    assert_close(stats.trimmed_unstable_rate(0.0), stats.unstable_rate);
+}
+
+#[test]
+fn empty() {
+    let stats = ErrorStatistics::new::<i32>(&[]);
+
+    assert!(stats.is_empty());
+    assert!(stats.unstable_rate.is_nan());
+    assert!(stats.percentile(50.0).is_nan());
+}
@@ -0,0 +1,124 @@
+use osu_db::{Mode, Replay};
+use rosu_pp::{Beatmap, GameMode};
+use rosu_ur_calc::{
+    beatmap_hash, try_calculate_report, verify_beatmap, Algorithm, UrCalculator, UrError,
+};
+
+fn parse_map_replay() -> (Beatmap, Replay) {
+    let map_path = "./test-data/maps/Gitaroo Man - Soft Machine (Ash) [Master Mode].osu";
+    let replay_path = "./test-data/replays/wolf_gitaroo.osr";
+
+    let map = Beatmap::from_path(map_path).expect("failed to parse map");
+    let replay = Replay::from_file(replay_path).expect("failed to parse replay");
+
+    (map, replay)
+}
+
+#[test]
+fn missing_replay_data() {
+    let (map, mut replay) = parse_map_replay();
+    replay.replay_data = None;
+
+    assert_eq!(
+        try_calculate_report(&map, &replay).unwrap_err(),
+        UrError::MissingReplayData
+    );
+    assert_eq!(
+        Algorithm::Baseline
+            .try_calculate_ur(&map, &replay)
+            .unwrap_err(),
+        UrError::MissingReplayData
+    );
+}
+
+#[test]
+fn no_hits_detected() {
+    let (map, mut replay) = parse_map_replay();
+    replay.replay_data = Some(Vec::new());
+
+    assert_eq!(
+        try_calculate_report(&map, &replay).unwrap_err(),
+        UrError::NoHitsDetected
+    );
+    assert_eq!(
+        Algorithm::Stable
+            .try_calculate_ur(&map, &replay)
+            .unwrap_err(),
+        UrError::NoHitsDetected
+    );
+}
+
+#[test]
+fn mode_mismatch() {
+    let (mut map, replay) = parse_map_replay();
+    map.mode = GameMode::Taiko;
+
+    assert_eq!(
+        try_calculate_report(&map, &replay).unwrap_err(),
+        UrError::ModeMismatch {
+            replay: GameMode::Osu,
+            map: GameMode::Taiko,
+        }
+    );
+}
+
+#[test]
+fn mode_mismatch_between_converts() {
+    let (mut map, mut replay) = parse_map_replay();
+    map.mode = GameMode::Mania;
+    replay.mode = Mode::Taiko;
+
+    assert_eq!(
+        try_calculate_report(&map, &replay).unwrap_err(),
+        UrError::ModeMismatch {
+            replay: GameMode::Taiko,
+            map: GameMode::Mania,
+        }
+    );
+}
+
+#[test]
+fn algorithm_rejects_other_modes() {
+    let (map, mut replay) = parse_map_replay();
+    replay.mode = Mode::Taiko;
+
+    assert_eq!(
+        Algorithm::Stable
+            .try_calculate_report(&map, &replay)
+            .unwrap_err(),
+        UrError::ModeMismatch {
+            replay: GameMode::Taiko,
+            map: GameMode::Osu,
+        }
+    );
+}
+
+#[test]
+fn beatmap_mismatch() {
+    let (_, mut replay) = parse_map_replay();
+    let map = std::fs::read("./test-data/maps/Gitaroo Man - Soft Machine (Ash) [Master Mode].osu")
+        .unwrap();
+    let other = std::fs::read(
+        "./test-data/maps/sphere - HIGH POWERED (TV Size) (Azunyan-) [POWER OVERLOAD EXPERT].osu",
+    )
+    .unwrap();
+
+    let hash = replay.beatmap_hash.clone().unwrap();
+    assert_eq!(beatmap_hash(&map), hash);
+    assert_eq!(verify_beatmap(&map, &replay), Ok(()));
+
+    assert_eq!(
+        verify_beatmap(&other, &replay).unwrap_err(),
+        UrError::BeatmapMismatch {
+            replay: hash.clone(),
+            map: beatmap_hash(&other),
+        }
+    );
+
+    replay.beatmap_hash = Some(hash.to_ascii_uppercase());
+    assert_eq!(verify_beatmap(&map, &replay), Ok(()));
+
+    // nothing to verify against
+    replay.beatmap_hash = None;
+    assert_eq!(verify_beatmap(&other, &replay), Ok(()));
+}
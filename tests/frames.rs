@@ -0,0 +1,140 @@
+use osu_db::{replay::Action, Replay};
+use rosu_ur_calc::{Buttons, Key, KeyPress, ReplayFrames, UrError};
+
+fn parse_replay() -> Replay {
+    let replay_path = "./test-data/replays/respektive_haitai.osr";
+
+    Replay::from_file(replay_path).expect("failed to parse replay")
+}
+
+fn action(delta: i64, x: f32, y: f32, z: f32) -> Action {
+    Action { delta, x, y, z }
+}
+
+#[test]
+fn decode_replay() {
+    let replay = parse_replay();
+    let frames = ReplayFrames::from_replay(&replay).unwrap();
+
+    assert!(frames.seed().is_some());
+    assert!(!frames.is_empty());
+    assert!(frames.windows(2).all(|pair| pair[0].time <= pair[1].time));
+
+    let mut prev = Buttons::NONE;
+
+    for frame in frames.iter() {
+        assert_eq!(frame.pressed, frame.buttons & !prev);
+        assert_eq!(frame.released, prev & !frame.buttons);
+        prev = frame.buttons;
+    }
+
+    assert!(frames.iter().any(|frame| frame.pressed.k1()));
+}
+
+#[test]
+fn special_frames() {
+    let actions = [
+        action(0, 256.0, -500.0, 0.0),
+        action(1000, 256.0, -500.0, 0.0),
+        action(10, 100.0, 100.0, 5.0),
+        action(-5, 0.0, 0.0, 0.0),
+        action(10, 110.0, 100.0, 7.0),
+        action(10, 120.0, 100.0, 2.0),
+        action(-12345, 0.0, 0.0, 42.0),
+    ];
+
+    let frames = ReplayFrames::from_actions(&actions);
+
+    assert_eq!(frames.seed(), Some(42));
+
+    let times: Vec<_> = frames.iter().map(|frame| frame.time).collect();
+    assert_eq!(times, [1010, 1015, 1025]);
+
+    // K1 sets M1 as well
+    assert_eq!(frames[0].pressed, Buttons::K1 | Buttons::M1);
+    assert_eq!(frames[0].pressed.distinct(), Buttons::K1);
+
+    // presses are relative to the previous kept frame
+    assert_eq!(frames[1].pressed, Buttons::M2);
+    assert_eq!(frames[1].released, Buttons::NONE);
+
+    assert_eq!(frames[2].pressed, Buttons::NONE);
+    assert_eq!(frames[2].released, Buttons::K1 | Buttons::M1);
+    assert!(frames[2].buttons.is_pressed());
+}
+
+#[test]
+fn missing_replay_data() {
+    let mut replay = parse_replay();
+    replay.replay_data = None;
+
+    assert_eq!(
+        ReplayFrames::from_replay(&replay).unwrap_err(),
+        UrError::MissingReplayData
+    );
+}
+
+#[test]
+fn keys() {
+    let keys: Vec<_> = (Buttons::K1 | Buttons::M1 | Buttons::M2).keys().collect();
+    assert_eq!(keys, [Key::K1, Key::M2]);
+
+    assert_eq!(Buttons::SMOKE.keys().count(), 0);
+}
+
+#[test]
+fn pressed_buttons() {
+    assert!((Buttons::K1 | Buttons::M1).is_pressed());
+    assert!(Buttons::M2.is_pressed());
+
+    // older clients only set the key
+    assert!(Buttons::K2.is_pressed());
+
+    assert!(!Buttons::SMOKE.is_pressed());
+    assert!(!Buttons::NONE.is_pressed());
+}
+
+#[test]
+fn key_presses() {
+    let k1 = (Buttons::K1 | Buttons::M1).bits() as f32;
+    let k2 = (Buttons::K2 | Buttons::M2).bits() as f32;
+
+    let actions = [
+        action(100, 0.0, 0.0, k1),
+        action(50, 0.0, 0.0, k1 + k2),
+        action(30, 0.0, 0.0, k2),
+        action(40, 0.0, 0.0, 0.0),
+        action(20, 0.0, 0.0, Buttons::M1.bits() as f32),
+        action(20, 0.0, 0.0, k1),
+    ];
+
+    let presses = ReplayFrames::from_actions(&actions).key_presses();
+
+    let expected = [
+        KeyPress {
+            key: Key::K1,
+            press_time: 100,
+            release_time: Some(180),
+        },
+        KeyPress {
+            key: Key::K2,
+            press_time: 150,
+            release_time: Some(220),
+        },
+        // M1 is masked out once K1 is pressed on top of it
+        KeyPress {
+            key: Key::M1,
+            press_time: 240,
+            release_time: Some(260),
+        },
+        KeyPress {
+            key: Key::K1,
+            press_time: 260,
+            release_time: None,
+        },
+    ];
+
+    assert_eq!(presses, expected);
+    assert_eq!(presses[0].hold_duration(), Some(80));
+    assert_eq!(presses[3].hold_duration(), None);
+}
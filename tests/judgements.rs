@@ -0,0 +1,181 @@
+use osu_db::Replay;
+use rosu_pp::Beatmap;
+use rosu_ur_calc::{validate, Algorithm, HitCounts, HitResult, UrCalculator, UrError};
+
+fn parse_map_replay(map_file: &str, replay_file: &str) -> (Beatmap, Replay) {
+    let map_path = format!("./test-data/maps/{map_file}.osu");
+    let replay_path = format!("./test-data/replays/{replay_file}.osr");
+
+    let map = Beatmap::from_path(map_path).expect("failed to parse map");
+    let replay = Replay::from_file(replay_path).expect("failed to parse replay");
+
+    (map, replay)
+}
+
+fn compare_hit_counts(map_file: &str, replay_file: &str) {
+    let (map, replay) = parse_map_replay(map_file, replay_file);
+    let report = Algorithm::Stable.calculate_report(&map, &replay);
+
+    assert_eq!(report.hit_counts(), Some(HitCounts::from_replay(&replay)));
+    assert_eq!(report.max_combo(), Some(usize::from(replay.max_combo)));
+}
+
+#[test]
+fn respektive_haitai() {
+    compare_hit_counts(
+        "Ayase Rie - Yuima-ruWorld TVver. (Fycho) [Extra]",
+        "respektive_haitai",
+    )
+}
+
+#[test]
+fn mismagius_usatei() {
+    compare_hit_counts("IOSYS - Usatei (Card N'FoRcE) [RUN!!]", "mismagius_usatei")
+}
+
+#[test]
+fn gn_barusa() {
+    compare_hit_counts(
+        "Nico Nico Douga - BARUSA of MIKOSU (DJPop) [TAG4]",
+        "gn_barusa",
+    )
+}
+
+#[test]
+fn rohulk_sanctus() {
+    compare_hit_counts(
+        "Feryquitous - Central Nucleus (Shiirn) [Sanctus Nexum]",
+        "rohulk_sanctus",
+    )
+}
+
+#[test]
+fn ekoro_barusa() {
+    compare_hit_counts(
+        "Nico Nico Douga - BARUSA of MIKOSU (DJPop) [TAG4]",
+        "ekoro_barusa",
+    )
+}
+
+#[test]
+fn gn_unforgiving() {
+    compare_hit_counts(
+        "Within Temptation - The Unforgiving (Armin) [Marathon]",
+        "gn_unforgiving",
+    )
+}
+
+#[test]
+fn judgements_agree_with_hit_errors() {
+    let (map, replay) = parse_map_replay(
+        "Chroma - sink to the deep sea world (None1637) [AR10]",
+        "respektive_sink",
+    );
+
+    let report = Algorithm::Stable.calculate_report(&map, &replay);
+    let n_misses = report
+        .judgements
+        .iter()
+        .filter(|judgement| judgement.result == HitResult::Miss)
+        .count();
+
+    // sliders whose head was missed can still be judged through their ticks
+    let n_tracked_without_head = report
+        .sliders
+        .iter()
+        .filter(|slider| !slider.head_hit && slider.result() != HitResult::Miss)
+        .count();
+
+    let n_unspun = report
+        .spinners
+        .iter()
+        .filter(|spinner| spinner.result() == HitResult::Miss)
+        .count();
+
+    assert_eq!(
+        report.judgements.len(),
+        report.n_judged + report.n_missed + report.n_skipped
+    );
+    assert_eq!(
+        n_misses + n_tracked_without_head,
+        report.n_missed + n_unspun
+    );
+}
+
+#[test]
+fn other_algorithms_have_no_judgements() {
+    let (map, replay) = parse_map_replay(
+        "Ayase Rie - Yuima-ruWorld TVver. (Fycho) [Extra]",
+        "respektive_haitai",
+    );
+
+    let report = Algorithm::Custom2.calculate_report(&map, &replay);
+
+    assert_eq!(report.hit_counts(), None);
+}
+
+#[test]
+fn validate_matching_replay() {
+    let (map, replay) =
+        parse_map_replay("IOSYS - Usatei (Card N'FoRcE) [RUN!!]", "mismagius_usatei");
+    let validation = validate(Algorithm::Stable, &map, &replay).unwrap();
+
+    assert!(validation.is_valid());
+    assert_eq!(validation.n100.simulated, 9);
+    assert_eq!(validation.max_combo.simulated, 314);
+}
+
+#[test]
+fn validate_max_combo() {
+    let (map, mut replay) = parse_map_replay(
+        "Euchaeta - Who's World (P_O) [Who Does This World Belong To]",
+        "respektive_whos_world",
+    );
+
+    // a dropped slider tail keeps the combo one below the map's maximum
+    let validation = validate(Algorithm::Stable, &map, &replay).unwrap();
+    assert!(validation.is_valid());
+    assert_eq!(validation.max_combo.simulated, 1224);
+
+    replay.max_combo = 1225;
+
+    let validation = validate(Algorithm::Stable, &map, &replay).unwrap();
+    let mismatches: Vec<_> = validation.mismatches().collect();
+
+    assert_eq!(mismatches.len(), 1);
+    assert_eq!(mismatches[0].0, "max_combo");
+    assert_eq!(mismatches[0].1.diff(), -1);
+}
+
+#[test]
+fn validate_mismatching_replay() {
+    let (map, mut replay) = parse_map_replay(
+        "Ayase Rie - Yuima-ruWorld TVver. (Fycho) [Extra]",
+        "respektive_haitai",
+    );
+
+    replay.count_300 -= 2;
+    replay.count_miss += 2;
+
+    let validation = validate(Algorithm::Stable, &map, &replay).unwrap();
+    let mismatches: Vec<_> = validation.mismatches().collect();
+
+    assert!(!validation.is_valid());
+    assert_eq!(mismatches.len(), 2);
+    assert_eq!(mismatches[0].0, "n300");
+    assert_eq!(mismatches[0].1.diff(), 2);
+    assert_eq!(mismatches[1].0, "n_miss");
+    assert_eq!(mismatches[1].1.diff(), -2);
+}
+
+#[test]
+fn validate_without_judgements() {
+    let (map, replay) = parse_map_replay(
+        "Ayase Rie - Yuima-ruWorld TVver. (Fycho) [Extra]",
+        "respektive_haitai",
+    );
+
+    let err = validate(Algorithm::Custom2, &map, &replay).unwrap_err();
+
+    assert_eq!(err, UrError::JudgementsNotSimulated);
+}
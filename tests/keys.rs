@@ -0,0 +1,93 @@
+use osu_db::Replay;
+use rosu_pp::Beatmap;
+use rosu_ur_calc::{
+    Algorithm, Buttons, Key, StableCalculator, TappingReport, UrCalculator, UrError,
+};
+
+fn parse_map_replay() -> (Beatmap, Replay) {
+    let map_path = "./test-data/maps/Nico Nico Douga - BARUSA of MIKOSU (DJPop) [TAG4].osu";
+    let replay_path = "./test-data/replays/ekoro_barusa.osr";
+
+    let map = Beatmap::from_path(map_path).expect("failed to parse map");
+    let replay = Replay::from_file(replay_path).expect("failed to parse replay");
+
+    (map, replay)
+}
+
+#[test]
+fn every_algorithm_attributes_keys() {
+    let (map, replay) = parse_map_replay();
+
+    for algorithm in Algorithm::ALL {
+        let report = algorithm.calculator().calculate_report(&map, &replay);
+
+        assert!(
+            report.hit_errors.iter().all(|hit| hit.key.is_some()),
+            "{algorithm:?} did not attribute every hit"
+        );
+    }
+}
+
+#[test]
+fn keys_match_taps() {
+    let (map, replay) = parse_map_replay();
+    let report = StableCalculator.calculate_report(&map, &replay);
+    let tapping = TappingReport::new(&map, &replay, &report).unwrap();
+
+    for (hit_error, tap) in report.hit_errors.iter().zip(tapping.taps.iter()) {
+        assert_eq!(hit_error.key, Some(tap.key));
+    }
+}
+
+#[test]
+fn key_statistics() {
+    let (map, replay) = parse_map_replay();
+    let report = StableCalculator.calculate_report(&map, &replay);
+
+    let mut n_used = 0;
+
+    for key in Key::ALL {
+        let errors: Vec<_> = report
+            .hit_errors
+            .iter()
+            .filter(|hit| hit.key == Some(key))
+            .map(|hit| hit.error)
+            .collect();
+
+        if errors.is_empty() {
+            continue;
+        }
+
+        n_used += 1;
+
+        let stats = report.key_statistics(key);
+        let mean = errors.iter().sum::<f64>() / errors.len() as f64;
+
+        assert!((stats.avg - mean).abs() < 1e-9);
+        assert!(stats.min >= report.statistics().min);
+        assert!(stats.max <= report.statistics().max);
+    }
+
+    assert!(n_used >= 2);
+}
+
+#[test]
+fn smoke_never_hits() {
+    let (map, mut replay) = parse_map_replay();
+
+    // press smoke instead of the keys, keeping the cursor on the objects
+    for action in replay.replay_data.as_mut().unwrap() {
+        if action.delta != -12345 && Buttons::from_bits(action.z as u8).is_pressed() {
+            action.z = f32::from(Buttons::SMOKE.bits());
+        }
+    }
+
+    for algorithm in Algorithm::ALL {
+        let result = algorithm.calculator().try_calculate_report(&map, &replay);
+
+        assert!(
+            matches!(result, Err(UrError::NoHitsDetected)),
+            "{algorithm:?} hit objects with smoke"
+        );
+    }
+}
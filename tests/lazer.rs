@@ -0,0 +1,89 @@
+use osu_db::Replay;
+use rosu_pp::Beatmap;
+use rosu_ur_calc::{
+    calculate_ur_lazer, Algorithm, HitPolicy, LazerCalculator, TimeScale, UrCalculator, UrOptions,
+};
+
+fn parse_map_replay(map_file: &str, replay_file: &str) -> (Beatmap, Replay) {
+    let map_path = format!("./test-data/maps/{map_file}.osu");
+    let replay_path = format!("./test-data/replays/{replay_file}.osr");
+
+    let map = Beatmap::from_path(map_path).expect("failed to parse map");
+    let replay = Replay::from_file(replay_path).expect("failed to parse replay");
+
+    (map, replay)
+}
+
+#[test]
+fn clean_replay_matches_stable() {
+    let (map, replay) = parse_map_replay(
+        "Ayase Rie - Yuima-ruWorld TVver. (Fycho) [Extra]",
+        "respektive_haitai",
+    );
+
+    let lazer = Algorithm::Lazer.calculate_report(&map, &replay);
+    let stable = Algorithm::Stable.calculate_report(&map, &replay);
+
+    assert_eq!(lazer.hit_errors, stable.hit_errors);
+    assert_eq!(calculate_ur_lazer(&map, &replay), lazer.unstable_rate);
+}
+
+#[test]
+fn no_stack_click_ignore() {
+    let (map, replay) = parse_map_replay(
+        "Chroma - sink to the deep sea world (None1637) [AR10]",
+        "respektive_sink",
+    );
+
+    let lazer = Algorithm::Lazer.calculate_report(&map, &replay);
+    let stable = Algorithm::Stable.calculate_report(&map, &replay);
+
+    assert!(lazer.n_missed < stable.n_missed);
+}
+
+#[test]
+fn object_ordered_is_stricter() {
+    let (map, replay) = parse_map_replay(
+        "Chroma - sink to the deep sea world (None1637) [AR10]",
+        "respektive_sink",
+    );
+
+    let start_time_ordered = LazerCalculator {
+        hit_policy: HitPolicy::StartTimeOrdered,
+    }
+    .calculate_report(&map, &replay);
+
+    let object_ordered = LazerCalculator {
+        hit_policy: HitPolicy::ObjectOrdered,
+    }
+    .calculate_report(&map, &replay);
+
+    assert_eq!(
+        start_time_ordered.hit_errors,
+        Algorithm::Lazer.calculate_report(&map, &replay).hit_errors
+    );
+    assert!(object_ordered.n_judged <= start_time_ordered.n_judged);
+}
+
+#[test]
+fn real_time_results_screen() {
+    let (map, replay) = parse_map_replay(
+        "Euchaeta - Who's World (P_O) [Who Does This World Belong To]",
+        "respektive_whos_world",
+    );
+
+    let options = UrOptions {
+        time_scale: TimeScale::MapTime,
+        ..Default::default()
+    };
+
+    let map_time = Algorithm::Lazer.calculate_report_with(&map, &replay, &options);
+    let real_time = Algorithm::Lazer.calculate_ur(&map, &replay);
+
+    assert!((real_time - map_time.unstable_rate / 1.5).abs() < 1e-9);
+
+    let real_time = Algorithm::Lazer.calculate_report(&map, &replay);
+
+    assert_eq!(LazerCalculator::displayed_unstable_rate(&real_time), 64.09);
+    assert_eq!(LazerCalculator::displayed_unstable_rate(&map_time), 64.09);
+}
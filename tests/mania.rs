@@ -0,0 +1,239 @@
+use osu_db::{replay::Action, ModSet, Mode, Replay};
+use rosu_pp::{Beatmap, BeatmapExt};
+use rosu_ur_calc::{
+    calculate_ur_mania, validate, Algorithm, HitCounts, HitResult, ManiaCalculator, UrCalculator,
+    UrError,
+};
+
+const OFFSETS: [i64; 4] = [-10, -3, 3, 10];
+const SCORE_V2: u32 = 1 << 29;
+
+fn parse_map_replay() -> (Beatmap, Replay) {
+    let map_path = "./test-data/maps/Ayase Rie - Yuima-ruWorld TVver. (Fycho) [Extra].osu";
+    let replay_path = "./test-data/replays/respektive_haitai.osr";
+
+    let map = Beatmap::from_path(map_path).expect("failed to parse map");
+    let mut replay = Replay::from_file(replay_path).expect("failed to parse replay");
+
+    replay.mode = Mode::Mania;
+    replay.mods = ModSet::empty();
+
+    (map, replay)
+}
+
+/// Hit every note of the converted map, cycling through [`OFFSETS`] for presses and releases.
+///
+/// Hold notes are released `release_shift` ms after their tail on top of the offset, but never
+/// before they were pressed.
+fn play(map: &Beatmap, replay: &mut Replay, release_shift: i64) {
+    let mut events: Vec<_> = map
+        .mania_hitobjects()
+        .iter()
+        .enumerate()
+        .flat_map(|(i, h)| {
+            let offset = OFFSETS[i % OFFSETS.len()];
+            let press = h.start_time.round() as i64 + offset;
+
+            let release = if h.end_time > h.start_time {
+                (h.end_time.round() as i64 + offset + release_shift).max(press + 1)
+            } else {
+                press + 1
+            };
+
+            [(press, h.column, true), (release, h.column, false)]
+        })
+        .collect();
+
+    // releases go first so a column can be pressed again within the same ms
+    events.sort_by_key(|(time, _, pressed)| (*time, *pressed));
+
+    let mut actions = Vec::with_capacity(events.len());
+    let mut prev_time = 0;
+    let mut columns = 0_u32;
+
+    for (time, column, pressed) in events {
+        if pressed {
+            columns |= 1 << column;
+        } else {
+            columns &= !(1 << column);
+        }
+
+        actions.push(Action {
+            delta: time - prev_time,
+            x: columns as f32,
+            y: 0.0,
+            z: 0.0,
+        });
+
+        prev_time = time;
+    }
+
+    replay.replay_data = Some(actions);
+}
+
+fn std_dev(values: &[f64]) -> f64 {
+    let mean = values.iter().sum::<f64>() / values.len() as f64;
+    let variance =
+        values.iter().map(|v| (v - mean) * (v - mean)).sum::<f64>() / values.len() as f64;
+
+    variance.sqrt()
+}
+
+#[test]
+fn perfect_play() {
+    let (map, mut replay) = parse_map_replay();
+    play(&map, &mut replay, 0);
+
+    let report = ManiaCalculator.calculate_report(&map, &replay);
+    let n_notes = map.mania_hitobjects().len();
+
+    assert_eq!(report.n_judged, n_notes);
+    assert_eq!(report.n_missed, 0);
+    assert_eq!(
+        report.hit_counts(),
+        Some(HitCounts {
+            n_max: n_notes,
+            ..Default::default()
+        })
+    );
+
+    let errors: Vec<_> = report.hit_errors.iter().map(|h| h.error).collect();
+    let expected = 10.0 * std_dev(&errors);
+
+    assert!((report.unstable_rate - expected).abs() < 1e-9);
+    assert!((calculate_ur_mania(&map, &replay) - expected).abs() < 1e-9);
+}
+
+#[test]
+fn validate_perfect_play() {
+    let (map, mut replay) = parse_map_replay();
+    play(&map, &mut replay, 0);
+
+    let n_notes = map.mania_hitobjects().len();
+
+    replay.count_geki = n_notes as u16;
+    replay.count_300 = 0;
+    replay.count_katsu = 0;
+    replay.count_100 = 0;
+    replay.count_50 = 0;
+    replay.count_miss = 0;
+    replay.max_combo = n_notes as u16;
+
+    // the algorithm only applies to osu!standard
+    let validation = validate(Algorithm::Stable, &map, &replay).unwrap();
+
+    assert_eq!(validation.n_max.simulated, n_notes);
+    assert!(
+        validation.is_valid(),
+        "{:?}",
+        validation.mismatches().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn columns() {
+    let (map, mut replay) = parse_map_replay();
+    play(&map, &mut replay, 0);
+
+    let hit_objects = map.mania_hitobjects();
+    let report = ManiaCalculator.calculate_report(&map, &replay);
+
+    assert!(report.columns.len() > 1);
+
+    let mut n_hit_errors = 0;
+
+    for (i, column) in report.columns.iter().enumerate() {
+        assert_eq!(column.column, i);
+
+        for hit_error in column.hit_errors.iter() {
+            let h = &hit_objects[hit_error.index];
+
+            assert_eq!(h.column, i);
+            assert!(
+                (hit_error.error - OFFSETS[hit_error.index % OFFSETS.len()] as f64).abs() <= 0.5
+            );
+        }
+
+        for release in column.release_errors.iter() {
+            let h = &hit_objects[release.index];
+
+            assert!(h.end_time > h.start_time);
+            assert!((release.error - OFFSETS[release.index % OFFSETS.len()] as f64).abs() <= 0.5);
+        }
+
+        let errors: Vec<_> = column.hit_errors.iter().map(|h| h.error).collect();
+
+        if !errors.is_empty() {
+            assert!((column.unstable_rate() - 10.0 * std_dev(&errors)).abs() < 1e-9);
+        }
+
+        n_hit_errors += column.hit_errors.len();
+    }
+
+    let n_holds = hit_objects
+        .iter()
+        .filter(|h| h.end_time > h.start_time)
+        .count();
+
+    let n_releases: usize = report.columns.iter().map(|c| c.release_errors.len()).sum();
+
+    assert_eq!(n_hit_errors, report.hit_errors.len());
+    assert!(n_holds > 0);
+    assert_eq!(n_releases, n_holds);
+}
+
+#[test]
+fn early_release() {
+    let (map, mut replay) = parse_map_replay();
+    play(&map, &mut replay, -250);
+
+    let report = ManiaCalculator.calculate_report(&map, &replay);
+    let hit_objects = map.mania_hitobjects();
+
+    let long_holds: Vec<_> = report
+        .judgements
+        .iter()
+        .filter(|judgement| {
+            let h = &hit_objects[judgement.index];
+
+            h.end_time - h.start_time > 300.0
+        })
+        .collect();
+
+    assert!(!long_holds.is_empty());
+
+    for judgement in long_holds {
+        assert_eq!(judgement.result, HitResult::Hit50);
+    }
+}
+
+#[test]
+fn score_v2_judges_tails() {
+    let (map, mut replay) = parse_map_replay();
+    play(&map, &mut replay, 0);
+
+    let v1 = ManiaCalculator.calculate_report(&map, &replay);
+
+    replay.mods = ModSet::from_bits(SCORE_V2);
+    let v2 = ManiaCalculator.calculate_report(&map, &replay);
+
+    let n_holds = map
+        .mania_hitobjects()
+        .iter()
+        .filter(|h| h.end_time > h.start_time)
+        .count();
+
+    assert_eq!(v2.judgements.len(), v1.judgements.len() + n_holds);
+    assert_eq!(v1.hit_errors, v2.hit_errors);
+}
+
+#[test]
+fn osu_replay_is_rejected() {
+    let (map, mut replay) = parse_map_replay();
+    replay.mode = Mode::Standard;
+
+    assert!(matches!(
+        ManiaCalculator.try_calculate_ur(&map, &replay),
+        Err(UrError::ModeMismatch { .. })
+    ));
+}
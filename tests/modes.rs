@@ -0,0 +1,80 @@
+use osu_db::{Mode, Replay};
+use rosu_pp::Beatmap;
+use rosu_ur_calc::{
+    calculate_report, calculate_ur_with, Algorithm, AnyModeCalculator, CatchCalculator,
+    ManiaCalculator, TaikoCalculator, UrCalculator,
+};
+
+fn parse_map_replay() -> (Beatmap, Replay) {
+    let map_path = "./test-data/maps/IOSYS - Usatei (Card N'FoRcE) [RUN!!].osu";
+    let replay_path = "./test-data/replays/mismagius_usatei.osr";
+
+    let map = Beatmap::from_path(map_path).expect("failed to parse map");
+    let replay = Replay::from_file(replay_path).expect("failed to parse replay");
+
+    (map, replay)
+}
+
+#[test]
+fn standard_uses_algorithm() {
+    let (map, replay) = parse_map_replay();
+
+    for algorithm in Algorithm::ALL {
+        let calculator = AnyModeCalculator { algorithm };
+
+        assert_eq!(
+            calculator.try_calculate_ur(&map, &replay),
+            algorithm.try_calculate_ur(&map, &replay)
+        );
+    }
+
+    assert_eq!(
+        calculate_report(&map, &replay).unstable_rate,
+        Algorithm::default().calculate_ur(&map, &replay)
+    );
+}
+
+#[test]
+fn converts_use_mode_calculator() {
+    let (map, mut replay) = parse_map_replay();
+
+    let calculators: [(Mode, &dyn UrCalculator); 3] = [
+        (Mode::Taiko, &TaikoCalculator),
+        (Mode::CatchTheBeat, &CatchCalculator),
+        (Mode::Mania, &ManiaCalculator),
+    ];
+
+    for (mode, calculator) in calculators {
+        replay.mode = mode;
+
+        let expected = calculator.try_calculate_report(&map, &replay);
+        let actual = AnyModeCalculator::default().try_calculate_report(&map, &replay);
+
+        assert_eq!(
+            actual.as_ref().map(|report| report.unstable_rate).ok(),
+            expected.as_ref().map(|report| report.unstable_rate).ok(),
+        );
+        assert_eq!(actual.err(), expected.err());
+    }
+}
+
+#[test]
+fn algorithm_is_ignored_for_other_modes() {
+    let (map, mut replay) = parse_map_replay();
+    replay.mode = Mode::Mania;
+
+    let expected = ManiaCalculator.try_calculate_ur(&map, &replay);
+
+    for algorithm in Algorithm::ALL {
+        let calculator = AnyModeCalculator { algorithm };
+
+        assert_eq!(calculator.try_calculate_ur(&map, &replay), expected);
+    }
+
+    if let Ok(expected) = expected {
+        assert_eq!(
+            calculate_ur_with(Algorithm::Stable, &map, &replay),
+            expected
+        );
+    }
+}
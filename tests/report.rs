@@ -0,0 +1,71 @@
+use osu_db::Replay;
+use rosu_pp::{Beatmap, BeatmapExt};
+use rosu_ur_calc::{
+    calculate_report, calculate_ur, calculate_ur_custom1, calculate_ur_stable, calculate_ur_with,
+    Algorithm, UrCalculator,
+};
+
+fn parse_map_replay(map_file: &str, replay_file: &str) -> (Beatmap, Replay) {
+    let map_path = format!("./test-data/maps/{map_file}.osu");
+    let replay_path = format!("./test-data/replays/{replay_file}.osr");
+
+    let map = Beatmap::from_path(map_path).expect("failed to parse map");
+    let replay = Replay::from_file(replay_path).expect("failed to parse replay");
+
+    (map, replay)
+}
+
+#[test]
+fn report_matches_ur() {
+    let (map, replay) = parse_map_replay(
+        "Ayase Rie - Yuima-ruWorld TVver. (Fycho) [Extra]",
+        "respektive_haitai",
+    );
+
+    let report = calculate_report(&map, &replay);
+
+    assert!((report.unstable_rate - calculate_ur(&map, &replay)).abs() <= f64::EPSILON);
+    assert!(report.minus_max <= report.minus_avg && report.minus_avg <= 0.0);
+    assert!(report.plus_max >= report.plus_avg && report.plus_avg >= 0.0);
+}
+
+#[test]
+fn report_counts_every_object() {
+    let (map, replay) = parse_map_replay(
+        "Chroma - sink to the deep sea world (None1637) [AR10]",
+        "respektive_sink",
+    );
+
+    let report = calculate_report(&map, &replay);
+    let n_objects = map.osu_hitobjects(replay.mods.bits()).len();
+
+    assert_eq!(report.n_judged, report.hit_errors.len());
+    assert_eq!(
+        report.n_judged + report.n_skipped + report.n_missed,
+        n_objects
+    );
+    assert!(report.n_missed > 0, "replay has misses");
+    assert!(report
+        .hit_errors
+        .windows(2)
+        .all(|pair| pair[0].index < pair[1].index));
+}
+
+#[test]
+fn dispatch_by_algorithm() {
+    let (map, replay) = parse_map_replay(
+        "sphere - HIGH POWERED (TV Size) (Azunyan-) [POWER OVERLOAD EXPERT]",
+        "respektive_high_powered",
+    );
+
+    let stable = calculate_ur_with(Algorithm::Stable, &map, &replay);
+    let custom1 = calculate_ur_with(Algorithm::Custom1, &map, &replay);
+
+    assert!((stable - calculate_ur_stable(&map, &replay)).abs() <= f64::EPSILON);
+    assert!((custom1 - calculate_ur_custom1(&map, &replay)).abs() <= f64::EPSILON);
+
+    for algorithm in Algorithm::ALL {
+        let report = algorithm.calculate_report(&map, &replay);
+        assert!(report.unstable_rate.is_finite(), "{algorithm:?}");
+    }
+}
@@ -0,0 +1,142 @@
+use osu_db::{replay::Action, ModSet, Replay};
+use rosu_pp::Beatmap;
+use rosu_ur_calc::{Algorithm, UrCalculator, UrOptions};
+
+/// A slider that ends long before its 50 hit window, followed by a circle.
+const SHORT_SLIDER_MAP: &str = "osu file format v14
+
+[General]
+Mode: 0
+
+[Difficulty]
+HPDrainRate:5
+CircleSize:4
+OverallDifficulty:5
+ApproachRate:9
+SliderMultiplier:1.4
+SliderTickRate:1
+
+[TimingPoints]
+0,500,4,2,0,100,1,0
+
+[HitObjects]
+100,100,1000,2,0,L|110:100,1,10
+300,300,1100,1,0
+";
+
+fn parse_map_replay(map_file: &str, replay_file: &str) -> (Beatmap, Replay) {
+    let map_path = format!("./test-data/maps/{map_file}.osu");
+    let replay_path = format!("./test-data/replays/{replay_file}.osr");
+
+    let map = Beatmap::from_path(map_path).expect("failed to parse map");
+    let replay = Replay::from_file(replay_path).expect("failed to parse replay");
+
+    (map, replay)
+}
+
+fn n_missed(map: &Beatmap, replay: &Replay, sliderbug_fixed: Option<bool>) -> usize {
+    let options = UrOptions {
+        sliderbug_fixed,
+        ..Default::default()
+    };
+
+    Algorithm::Circleguard
+        .calculate_report_with(map, replay, &options)
+        .n_missed
+}
+
+/// A replay that skips the slider and clicks the circle on time while the slider's 50 hit
+/// window is still open.
+fn short_slider_map_replay() -> (Beatmap, Replay) {
+    let map = Beatmap::from_bytes(SHORT_SLIDER_MAP.as_bytes()).expect("failed to parse map");
+    let mut replay =
+        Replay::from_file("./test-data/replays/gn_strange.osr").expect("failed to parse replay");
+
+    let action = |delta, z| Action {
+        delta,
+        x: 300.0,
+        y: 300.0,
+        z,
+    };
+
+    replay.mods = ModSet::empty();
+    replay.replay_data = Some(vec![action(1050, 0.0), action(50, 5.0), action(50, 0.0)]);
+
+    (map, replay)
+}
+
+#[test]
+fn version_decides_fix() {
+    let (_, mut replay) = parse_map_replay(
+        "DJ Sharpnel - StrangeProgram (happy30) [Lesjuh's TAG]",
+        "gn_strange",
+    );
+
+    let options = UrOptions::default();
+
+    replay.version = UrOptions::SLIDERBUG_FIX_VERSION - 1;
+    assert!(!options.sliderbug_fixed(&replay));
+
+    replay.version = UrOptions::SLIDERBUG_FIX_VERSION;
+    assert!(options.sliderbug_fixed(&replay));
+
+    let options = UrOptions {
+        sliderbug_fixed: Some(false),
+        ..Default::default()
+    };
+
+    assert!(!options.sliderbug_fixed(&replay));
+}
+
+#[test]
+fn pre_fix_replay() {
+    let (map, mut replay) = parse_map_replay(
+        "DJ Sharpnel - StrangeProgram (happy30) [Lesjuh's TAG]",
+        "gn_strange",
+    );
+
+    replay.version = 20190101;
+
+    assert_eq!(n_missed(&map, &replay, None), 40);
+    assert_eq!(n_missed(&map, &replay, Some(false)), 40);
+    assert_eq!(n_missed(&map, &replay, Some(true)), 22);
+}
+
+#[test]
+fn post_fix_replay() {
+    let (map, mut replay) = parse_map_replay(
+        "DJ Sharpnel - StrangeProgram (happy30) [Lesjuh's TAG]",
+        "gn_strange",
+    );
+
+    replay.version = 20200101;
+
+    assert_eq!(n_missed(&map, &replay, None), 22);
+    assert_eq!(n_missed(&map, &replay, Some(true)), 22);
+    assert_eq!(n_missed(&map, &replay, Some(false)), 40);
+}
+
+#[test]
+fn stable_follows_override() {
+    let (map, mut replay) = short_slider_map_replay();
+
+    let n_judged = |replay: &Replay, sliderbug_fixed: Option<bool>| {
+        let options = UrOptions {
+            sliderbug_fixed,
+            ..Default::default()
+        };
+
+        Algorithm::Stable
+            .try_calculate_report_with(&map, replay, &options)
+            .map_or(0, |report| report.n_judged)
+    };
+
+    // before the fix the slider stops locking the circle once it ends
+    replay.version = 20190101;
+    assert_eq!(n_judged(&replay, None), 1);
+    assert_eq!(n_judged(&replay, Some(true)), 0);
+
+    replay.version = 20200101;
+    assert_eq!(n_judged(&replay, None), 0);
+    assert_eq!(n_judged(&replay, Some(false)), 1);
+}
@@ -0,0 +1,232 @@
+use osu_db::{replay::Action, ModSet, Replay};
+use rosu_pp::{
+    osu::{NestedObjectKind, OsuObjectKind},
+    parse::Pos2,
+    Beatmap, BeatmapExt,
+};
+use rosu_ur_calc::{Algorithm, Buttons, HitResult, SliderResult, UrCalculator};
+
+fn parse_map_replay(map_file: &str, replay_file: &str) -> (Beatmap, Replay) {
+    let map_path = format!("./test-data/maps/{map_file}.osu");
+    let replay_path = format!("./test-data/replays/{replay_file}.osr");
+
+    let map = Beatmap::from_path(map_path).expect("failed to parse map");
+    let replay = Replay::from_file(replay_path).expect("failed to parse replay");
+
+    (map, replay)
+}
+
+#[test]
+fn every_slider_is_tracked() {
+    let (map, replay) = parse_map_replay(
+        "Chroma - sink to the deep sea world (None1637) [AR10]",
+        "respektive_sink",
+    );
+
+    let report = Algorithm::Stable.calculate_report(&map, &replay);
+
+    assert_eq!(report.sliders.len(), map.n_sliders as usize);
+    assert!(report
+        .sliders
+        .windows(2)
+        .all(|window| window[0].index < window[1].index));
+
+    for slider in report.sliders.iter() {
+        let judgement = report
+            .judgements
+            .iter()
+            .find(|judgement| judgement.index == slider.index)
+            .expect("missing slider judgement");
+
+        assert_eq!(judgement.result, slider.result());
+    }
+}
+
+#[test]
+fn slider_breaks() {
+    let (map, replay) = parse_map_replay(
+        "Chroma - sink to the deep sea world (None1637) [AR10]",
+        "respektive_sink",
+    );
+
+    let report = Algorithm::Stable.calculate_report(&map, &replay);
+    let breaks: Vec<_> = report
+        .sliders
+        .iter()
+        .filter(|slider| slider.is_slider_break())
+        .map(|slider| slider.index)
+        .collect();
+
+    assert_eq!(
+        breaks,
+        [
+            822, 1000, 1008, 1012, 1036, 1044, 1053, 1054, 1062, 1063, 1068, 1144, 1146, 1148,
+            1250, 1479, 1482, 1488, 1495, 1530
+        ]
+    );
+}
+
+/// Track the first slider with at least three ticks while holding the cursor `offset` away
+/// from each nested object, releasing the keys once between the first two ticks if `release`.
+fn follow_slider(offset: Pos2, release: bool) -> SliderResult {
+    let (map, mut replay) = parse_map_replay(
+        "Within Temptation - The Unforgiving (Armin) [Marathon]",
+        "respektive_whos_world",
+    );
+
+    replay.mods = ModSet::empty();
+
+    let hit_objects = map.osu_hitobjects(0);
+
+    let (index, h, nested) = hit_objects
+        .iter()
+        .enumerate()
+        .find_map(|(i, h)| {
+            let OsuObjectKind::Slider(ref slider) = h.kind else {
+                return None;
+            };
+
+            let mut nested = slider.nested_objects.clone();
+            nested.sort_by(|a, b| a.start_time.total_cmp(&b.start_time));
+
+            let n_ticks = nested
+                .iter()
+                .filter(|nested| nested.kind == NestedObjectKind::Tick)
+                .count();
+
+            (n_ticks >= 3).then_some((i, h, nested))
+        })
+        .expect("no slider with ticks");
+
+    let pressed = f32::from((Buttons::K1 | Buttons::M1).bits());
+    let head = h.pos + h.stack_offset;
+
+    let mut frames = vec![
+        (h.start_time - 100.0, head, 0.0),
+        (h.start_time, head, pressed),
+    ];
+
+    for (i, object) in nested.iter().enumerate() {
+        let pos = object.pos + h.stack_offset + offset;
+
+        if release && i == 1 {
+            let release_time = (nested[0].start_time + object.start_time) / 2.0;
+            frames.push((release_time, pos, 0.0));
+        }
+
+        frames.push((object.start_time, pos, pressed));
+    }
+
+    let mut time = 0;
+
+    let actions = frames
+        .into_iter()
+        .map(|(frame_time, pos, z)| {
+            let frame_time = frame_time.round() as i64;
+            let delta = frame_time - time;
+            time = frame_time;
+
+            Action {
+                delta,
+                x: pos.x,
+                y: pos.y,
+                z,
+            }
+        })
+        .collect();
+
+    replay.replay_data = Some(actions);
+
+    let report = Algorithm::Stable.calculate_report(&map, &replay);
+
+    report
+        .sliders
+        .into_iter()
+        .find(|slider| slider.index == index)
+        .expect("missing slider")
+}
+
+/// Circle radius of the map the sliders are followed on.
+fn circle_radius() -> f32 {
+    let map_path = "./test-data/maps/Within Temptation - The Unforgiving (Armin) [Marathon].osu";
+    let map = Beatmap::from_path(map_path).expect("failed to parse map");
+
+    64.0 * (1.0 - 0.7 * (map.cs - 5.0) / 5.0) / 2.0
+}
+
+#[test]
+fn follow_circle_grows_while_tracking() {
+    // outside of the circle but inside of the grown follow circle
+    let offset = Pos2 {
+        x: 1.6 * circle_radius(),
+        y: 0.0,
+    };
+
+    let slider = follow_slider(offset, false);
+
+    assert!(slider.head_hit);
+    assert_eq!(slider.ticks_missed, 0);
+    assert_eq!(slider.repeats_missed, 0);
+    assert!(slider.tail_hit);
+}
+
+#[test]
+fn follow_circle_shrinks_on_release() {
+    let offset = Pos2 {
+        x: 1.6 * circle_radius(),
+        y: 0.0,
+    };
+
+    let slider = follow_slider(offset, true);
+
+    // pressing again is not enough to regain tracking outside of the circle
+    assert!(slider.head_hit);
+    assert_eq!(slider.ticks_hit, 1);
+    assert!(slider.ticks_missed >= 2);
+    assert!(!slider.tail_hit);
+}
+
+#[test]
+fn missed_tails_only() {
+    let (map, replay) = parse_map_replay(
+        "Euchaeta - Who's World (P_O) [Who Does This World Belong To]",
+        "respektive_whos_world",
+    );
+
+    let report = Algorithm::Stable.calculate_report(&map, &replay);
+
+    assert!(report
+        .sliders
+        .iter()
+        .all(|slider| !slider.is_slider_break()));
+
+    let dropped_tails: Vec<_> = report
+        .sliders
+        .iter()
+        .filter(|slider| !slider.tail_hit)
+        .collect();
+
+    assert_eq!(dropped_tails.len(), 1);
+    assert_eq!(dropped_tails[0].result(), HitResult::Hit100);
+
+    // the dropped tail only withholds its own point of combo
+    let slider = dropped_tails[0];
+    let nested = 1 + slider.ticks_hit + slider.repeats_hit;
+
+    assert_eq!(slider.leading_combo, nested);
+    assert_eq!(slider.max_combo, nested);
+    assert_eq!(slider.trailing_combo, 0);
+    assert_eq!(report.max_combo(), Some(usize::from(replay.max_combo)));
+}
+
+#[test]
+fn other_algorithms_do_not_track_sliders() {
+    let (map, replay) = parse_map_replay(
+        "Chroma - sink to the deep sea world (None1637) [AR10]",
+        "respektive_sink",
+    );
+
+    let report = Algorithm::Custom2.calculate_report(&map, &replay);
+
+    assert!(report.sliders.is_empty());
+}
@@ -0,0 +1,117 @@
+use osu_db::{Mod, Replay};
+use rosu_pp::Beatmap;
+use rosu_ur_calc::{simulate_spinners, Algorithm, HitResult, TimeScale, UrCalculator, UrOptions};
+
+fn parse_map_replay(map_file: &str, replay_file: &str) -> (Beatmap, Replay) {
+    let map_path = format!("./test-data/maps/{map_file}.osu");
+    let replay_path = format!("./test-data/replays/{replay_file}.osr");
+
+    let map = Beatmap::from_path(map_path).expect("failed to parse map");
+    let replay = Replay::from_file(replay_path).expect("failed to parse replay");
+
+    (map, replay)
+}
+
+#[test]
+fn every_spinner_is_simulated() {
+    let (map, replay) = parse_map_replay(
+        "Within Temptation - The Unforgiving (Armin) [Marathon]",
+        "gn_unforgiving",
+    );
+
+    let spinners = simulate_spinners(&map, &replay).unwrap();
+
+    assert_eq!(spinners.len(), map.n_spinners as usize);
+
+    for spinner in spinners.iter() {
+        assert!(spinner.is_cleared());
+        assert_eq!(spinner.result(), HitResult::Hit300);
+        assert!(spinner.max_rpm >= spinner.avg_rpm);
+        assert!(spinner.max_rpm <= 480.0);
+    }
+}
+
+#[test]
+fn report_contains_spinners() {
+    let (map, replay) = parse_map_replay(
+        "Nico Nico Douga - BARUSA of MIKOSU (DJPop) [TAG4]",
+        "ekoro_barusa",
+    );
+
+    let report = Algorithm::Stable.calculate_report(&map, &replay);
+    let spinners = simulate_spinners(&map, &replay).unwrap();
+
+    assert_eq!(report.spinners, spinners);
+    assert_eq!(report.n_skipped, spinners.len());
+}
+
+#[test]
+fn unheld_spinner_is_missed() {
+    let (map, mut replay) = parse_map_replay(
+        "Within Temptation - The Unforgiving (Armin) [Marathon]",
+        "gn_unforgiving",
+    );
+
+    for action in replay.replay_data.as_mut().unwrap() {
+        action.z = 0.0;
+    }
+
+    let spinners = simulate_spinners(&map, &replay).unwrap();
+
+    assert!(spinners.iter().all(|spinner| spinner.rotations == 0.0));
+    assert!(spinners
+        .iter()
+        .filter(|spinner| spinner.required_rotations > 0.0)
+        .all(|spinner| spinner.result() == HitResult::Miss));
+
+    replay.mods = replay.mods.with(Mod::Relax);
+
+    let relaxed = simulate_spinners(&map, &replay).unwrap();
+
+    assert!(relaxed.iter().all(|spinner| spinner.rotations > 0.0));
+}
+
+#[test]
+fn spun_out() {
+    let (map, mut replay) = parse_map_replay(
+        "Within Temptation - The Unforgiving (Armin) [Marathon]",
+        "gn_unforgiving",
+    );
+
+    replay.mods = replay.mods.with(Mod::SpunOut);
+
+    let spinners = simulate_spinners(&map, &replay).unwrap();
+
+    for spinner in spinners {
+        assert!((spinner.avg_rpm - 286.0).abs() < 1e-6);
+        assert!(spinner.is_cleared());
+    }
+}
+
+#[test]
+fn rpm_in_real_time() {
+    let (map, mut replay) = parse_map_replay(
+        "Within Temptation - The Unforgiving (Armin) [Marathon]",
+        "gn_unforgiving",
+    );
+
+    replay.mods = replay.mods.with(Mod::SpunOut).with(Mod::DoubleTime);
+
+    let spinners = simulate_spinners(&map, &replay).unwrap();
+    let report = Algorithm::Stable.calculate_report_with(
+        &map,
+        &replay,
+        &UrOptions {
+            time_scale: TimeScale::MapTime,
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(report.spinners.len(), spinners.len());
+
+    for (spinner, map_time) in spinners.iter().zip(&report.spinners) {
+        assert!((spinner.avg_rpm - 286.0 * 1.5).abs() < 1e-6);
+        assert!((map_time.avg_rpm - 286.0).abs() < 1e-6);
+        assert_eq!(spinner.rotations, map_time.rotations);
+    }
+}
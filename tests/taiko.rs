@@ -0,0 +1,116 @@
+use osu_db::{replay::Action, ModSet, Mode, Replay};
+use rosu_pp::{Beatmap, BeatmapExt};
+use rosu_ur_calc::{calculate_ur_taiko, HitCounts, TaikoCalculator, UrCalculator, UrError};
+
+const OFFSETS: [i64; 4] = [-12, -4, 4, 12];
+
+fn parse_map_replay() -> (Beatmap, Replay) {
+    let map_path = "./test-data/maps/Ayase Rie - Yuima-ruWorld TVver. (Fycho) [Extra].osu";
+    let replay_path = "./test-data/replays/respektive_haitai.osr";
+
+    let map = Beatmap::from_path(map_path).expect("failed to parse map");
+    let mut replay = Replay::from_file(replay_path).expect("failed to parse replay");
+
+    replay.mode = Mode::Taiko;
+    replay.mods = ModSet::empty();
+
+    (map, replay)
+}
+
+/// Hit every note of the converted map with the matching drum side, cycling through
+/// [`OFFSETS`].
+fn play(map: &Beatmap, replay: &mut Replay, swap_colours: bool) {
+    let mut presses: Vec<_> = map
+        .taiko_hitobjects()
+        .iter()
+        .filter(|h| h.is_hit)
+        .enumerate()
+        .map(|(i, h)| {
+            let time = h.start_time.round() as i64 + OFFSETS[i % OFFSETS.len()];
+            let key = if h.is_rim != swap_colours { 2.0 } else { 1.0 };
+
+            (time, key)
+        })
+        .collect();
+
+    presses.sort_by_key(|(time, _)| *time);
+
+    let mut actions = Vec::with_capacity(presses.len() * 2);
+    let mut prev_time = 0;
+
+    for (time, key) in presses {
+        for (time, z) in [(time, key), (time + 1, 0.0)] {
+            actions.push(Action {
+                delta: time - prev_time,
+                x: 0.0,
+                y: 0.0,
+                z,
+            });
+
+            prev_time = time;
+        }
+    }
+
+    replay.replay_data = Some(actions);
+}
+
+fn std_dev(values: &[f64]) -> f64 {
+    let mean = values.iter().sum::<f64>() / values.len() as f64;
+    let variance =
+        values.iter().map(|v| (v - mean) * (v - mean)).sum::<f64>() / values.len() as f64;
+
+    variance.sqrt()
+}
+
+#[test]
+fn perfect_play() {
+    let (map, mut replay) = parse_map_replay();
+    play(&map, &mut replay, false);
+
+    let report = TaikoCalculator.calculate_report(&map, &replay);
+    let n_notes = map.taiko_hitobjects().iter().filter(|h| h.is_hit).count();
+
+    assert_eq!(report.n_judged, n_notes);
+    assert_eq!(report.n_missed, 0);
+    assert_eq!(
+        report.hit_counts(),
+        Some(HitCounts {
+            n300: n_notes,
+            ..Default::default()
+        })
+    );
+
+    for (i, hit_error) in report.hit_errors.iter().enumerate() {
+        assert!((hit_error.error - OFFSETS[i % OFFSETS.len()] as f64).abs() <= 0.5);
+    }
+
+    let errors: Vec<_> = report.hit_errors.iter().map(|h| h.error).collect();
+    let expected = 10.0 * std_dev(&errors);
+
+    assert!((report.unstable_rate - expected).abs() < 1e-9);
+    assert!((calculate_ur_taiko(&map, &replay) - expected).abs() < 1e-9);
+}
+
+#[test]
+fn wrong_colour_is_a_miss() {
+    let (map, mut replay) = parse_map_replay();
+    play(&map, &mut replay, true);
+
+    assert_eq!(
+        TaikoCalculator
+            .try_calculate_report(&map, &replay)
+            .unwrap_err(),
+        UrError::NoHitsDetected
+    );
+}
+
+#[test]
+fn osu_replay_is_rejected() {
+    let (map, mut replay) = parse_map_replay();
+    replay.mode = Mode::Standard;
+
+    assert!(matches!(
+        TaikoCalculator.try_calculate_ur(&map, &replay),
+        Err(UrError::ModeMismatch { .. })
+    ));
+}
@@ -0,0 +1,101 @@
+use osu_db::{Mode, Replay};
+use rosu_pp::{Beatmap, BeatmapExt};
+use rosu_ur_calc::{
+    Key, StableCalculator, TappingReport, TimeScale, UrCalculator, UrError, UrOptions,
+};
+
+fn parse_map_replay() -> (Beatmap, Replay) {
+    let map_path = "./test-data/maps/Nico Nico Douga - BARUSA of MIKOSU (DJPop) [TAG4].osu";
+    let replay_path = "./test-data/replays/ekoro_barusa.osr";
+
+    let map = Beatmap::from_path(map_path).expect("failed to parse map");
+    let replay = Replay::from_file(replay_path).expect("failed to parse replay");
+
+    (map, replay)
+}
+
+#[test]
+fn every_hit_has_a_tap() {
+    let (map, replay) = parse_map_replay();
+    let report = StableCalculator.calculate_report(&map, &replay);
+    let tapping = TappingReport::new(&map, &replay, &report).unwrap();
+
+    assert_eq!(tapping.taps.len(), report.hit_errors.len());
+
+    // the replay is played with HalfTime while frames and objects are in map time
+    let hit_objects = map.osu_hitobjects(0);
+
+    for (tap, hit_error) in tapping.taps.iter().zip(report.hit_errors.iter()) {
+        let hit_time =
+            hit_objects[hit_error.index].start_time + hit_error.error * report.clock_rate;
+
+        assert_eq!(tap.index, hit_error.index);
+        assert_eq!(Some(tap.key), hit_error.key);
+        assert_eq!(tap.press_time, hit_time.round() as i32);
+    }
+
+    let n_presses: usize = Key::ALL.iter().map(|&key| tapping.press_count(key)).sum();
+    let n_taps: usize = Key::ALL.iter().map(|&key| tapping.tap_count(key)).sum();
+
+    assert_eq!(n_presses, tapping.presses.len());
+    assert_eq!(n_taps, tapping.taps.len());
+    assert!(n_presses >= n_taps);
+}
+
+#[test]
+fn hold_durations() {
+    let (map, replay) = parse_map_replay();
+    let report = StableCalculator.calculate_report(&map, &replay);
+    let tapping = TappingReport::new(&map, &replay, &report).unwrap();
+
+    let all = tapping.hold_statistics(None);
+
+    assert!(all.min >= 0.0);
+    assert!(all.avg > 0.0);
+
+    for key in Key::ALL {
+        if tapping.tap_count(key) > 0 {
+            let stats = tapping.hold_statistics(Some(key));
+
+            assert!(stats.min >= all.min);
+            assert!(stats.max <= all.max);
+        }
+    }
+
+    let ratio = tapping.alternation_ratio();
+    assert!(ratio > 0.0 && ratio < 1.0);
+}
+
+#[test]
+fn real_time_report() {
+    let (map, replay) = parse_map_replay();
+
+    let options = UrOptions {
+        time_scale: TimeScale::MapTime,
+        ..Default::default()
+    };
+
+    let map_time = StableCalculator.calculate_report_with(&map, &replay, &options);
+    let real_time = StableCalculator.calculate_report(&map, &replay);
+
+    assert_eq!(real_time.time_scale, TimeScale::RealTime);
+    assert!((real_time.clock_rate - 0.75).abs() < f64::EPSILON);
+    assert_ne!(map_time.hit_errors, real_time.hit_errors);
+
+    let map_time_taps = TappingReport::new(&map, &replay, &map_time).unwrap().taps;
+    let real_time_taps = TappingReport::new(&map, &replay, &real_time).unwrap().taps;
+
+    assert_eq!(map_time_taps, real_time_taps);
+}
+
+#[test]
+fn taiko_replay_is_rejected() {
+    let (map, mut replay) = parse_map_replay();
+    let report = StableCalculator.calculate_report(&map, &replay);
+    replay.mode = Mode::Taiko;
+
+    assert!(matches!(
+        TappingReport::new(&map, &replay, &report),
+        Err(UrError::ModeMismatch { .. })
+    ));
+}
@@ -0,0 +1,196 @@
+use osu_db::{Mod, Replay};
+use rosu_pp::{Beatmap, GameMode};
+use rosu_ur_calc::{
+    HitCounts, HitErrorReport, Judgement, TimeScale, UrError, UrOptions, UrTracker,
+};
+
+fn parse_map_replay(map_file: &str, replay_file: &str) -> (Beatmap, Replay) {
+    let map_path = format!("./test-data/maps/{map_file}.osu");
+    let replay_path = format!("./test-data/replays/{replay_file}.osr");
+
+    let map = Beatmap::from_path(map_path).expect("failed to parse map");
+    let replay = Replay::from_file(replay_path).expect("failed to parse replay");
+
+    (map, replay)
+}
+
+fn track(map: &Beatmap, replay: &Replay, options: &UrOptions) -> (Vec<Judgement>, UrTracker) {
+    let options = UrOptions {
+        sliderbug_fixed: Some(options.sliderbug_fixed(replay)),
+        ..options.clone()
+    };
+
+    let mut tracker = UrTracker::with_options(map, replay.mods, &options).unwrap();
+    let mut emitted = Vec::new();
+
+    for action in replay.replay_data.as_deref().unwrap() {
+        emitted.extend_from_slice(tracker.push_action(action));
+    }
+
+    (emitted, tracker)
+}
+
+/// Counts and unstable rate in map time of the batch calculation before it was built on the
+/// tracker.
+struct Expected {
+    map_file: &'static str,
+    replay_file: &'static str,
+    unstable_rate: f64,
+    hit_counts: HitCounts,
+    n_judged: usize,
+    n_skipped: usize,
+}
+
+const EKORO_BARUSA: Expected = Expected {
+    map_file: "Nico Nico Douga - BARUSA of MIKOSU (DJPop) [TAG4]",
+    replay_file: "ekoro_barusa",
+    unstable_rate: 115.32513020628346,
+    hit_counts: HitCounts {
+        n_max: 0,
+        n300: 1098,
+        n200: 0,
+        n100: 1,
+        n50: 0,
+        n_miss: 0,
+    },
+    n_judged: 1095,
+    n_skipped: 4,
+};
+
+fn assert_expected(tracked: &HitErrorReport, expected: &Expected) {
+    assert!((tracked.unstable_rate - expected.unstable_rate).abs() < 1e-9);
+    assert_eq!(tracked.hit_counts(), Some(expected.hit_counts));
+    assert_eq!(tracked.n_judged, expected.n_judged);
+    assert_eq!(tracked.n_skipped, expected.n_skipped);
+    assert_eq!(tracked.n_missed, 0);
+}
+
+#[test]
+fn golden_values() {
+    let expected = [
+        EKORO_BARUSA,
+        Expected {
+            map_file: "sphere - HIGH POWERED (TV Size) (Azunyan-) [POWER OVERLOAD EXPERT]",
+            replay_file: "respektive_high_powered",
+            unstable_rate: 90.79778361089288,
+            hit_counts: HitCounts {
+                n300: 424,
+                n100: 2,
+                ..Default::default()
+            },
+            n_judged: 425,
+            n_skipped: 1,
+        },
+        Expected {
+            map_file: "Euchaeta - Who's World (P_O) [Who Does This World Belong To]",
+            replay_file: "respektive_whos_world",
+            unstable_rate: 96.1392121024138,
+            hit_counts: HitCounts {
+                n300: 971,
+                n100: 3,
+                ..Default::default()
+            },
+            n_judged: 974,
+            n_skipped: 0,
+        },
+    ];
+
+    for expected in expected {
+        let (map, replay) = parse_map_replay(expected.map_file, expected.replay_file);
+
+        let options = UrOptions {
+            time_scale: TimeScale::MapTime,
+            ..Default::default()
+        };
+
+        let (emitted, tracker) = track(&map, &replay, &options);
+
+        assert!((tracker.unstable_rate() - expected.unstable_rate).abs() < 1e-9);
+
+        let tracked = tracker.finish().unwrap();
+        assert_expected(&tracked, &expected);
+
+        // objects are only judged once their judgement is final
+        assert!(!emitted.is_empty());
+
+        for judgement in emitted {
+            assert_eq!(judgement, tracked.judgements[judgement.index]);
+        }
+    }
+}
+
+#[test]
+fn judgements_come_in_while_playing() {
+    let (map, replay) = parse_map_replay(
+        "Nico Nico Douga - BARUSA of MIKOSU (DJPop) [TAG4]",
+        "ekoro_barusa",
+    );
+
+    let actions = replay.replay_data.as_deref().unwrap();
+    let mut tracker = UrTracker::new(&map, replay.mods).unwrap();
+
+    for action in actions[..actions.len() / 2].iter() {
+        tracker.push_action(action);
+    }
+
+    let n_half = tracker.judgements().len();
+
+    assert!(n_half > 0);
+    assert!(n_half < map.hit_objects.len());
+    assert!(tracker.unstable_rate() > 0.0);
+}
+
+#[test]
+fn real_time() {
+    let (map, mut replay) = parse_map_replay(EKORO_BARUSA.map_file, EKORO_BARUSA.replay_file);
+
+    replay.mods = replay.mods.with(Mod::DoubleTime);
+
+    let options = UrOptions {
+        time_scale: TimeScale::RealTime,
+        ..Default::default()
+    };
+
+    let (_, tracker) = track(&map, &replay, &options);
+
+    assert!((tracker.unstable_rate() - 76.88342013752288).abs() < 1e-9);
+
+    let tracked = tracker.finish().unwrap();
+
+    assert_eq!(tracked.clock_rate, 1.5);
+    assert_expected(
+        &tracked,
+        &Expected {
+            unstable_rate: 76.88342013752288,
+            ..EKORO_BARUSA
+        },
+    );
+}
+
+#[test]
+fn nothing_hit() {
+    let (map, replay) = parse_map_replay(
+        "Nico Nico Douga - BARUSA of MIKOSU (DJPop) [TAG4]",
+        "ekoro_barusa",
+    );
+
+    let tracker = UrTracker::new(&map, replay.mods).unwrap();
+
+    assert_eq!(tracker.unstable_rate(), 0.0);
+    assert_eq!(tracker.finish().unwrap_err(), UrError::NoHitsDetected);
+}
+
+#[test]
+fn taiko_map_is_rejected() {
+    let (mut map, replay) = parse_map_replay(
+        "Nico Nico Douga - BARUSA of MIKOSU (DJPop) [TAG4]",
+        "ekoro_barusa",
+    );
+
+    map.mode = GameMode::Taiko;
+
+    assert!(matches!(
+        UrTracker::new(&map, replay.mods),
+        Err(UrError::ModeMismatch { .. })
+    ));
+}